@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use arrow_array::cast::downcast_array;
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, ArrowPrimitiveType, PrimitiveArray, RecordBatch};
+use arrow_buffer::{ArrowNativeType, ScalarBuffer};
+use arrow_schema::{Field, Schema};
+use num::Num;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+use crate::namedarray::{NamedArray, Result};
+
+pub trait ArrowNative: Num + Copy + ArrowNativeType {
+    type ArrowType: ArrowPrimitiveType<Native = Self>;
+}
+
+macro_rules! impl_arrow_native {
+    ($($native:ty => $arrow:ty),*) => {
+        $(
+            impl ArrowNative for $native {
+                type ArrowType = $arrow;
+            }
+        )*
+    };
+}
+
+impl_arrow_native!(
+    f32 => Float32Type,
+    f64 => Float64Type,
+    i8 => Int8Type,
+    i16 => Int16Type,
+    i32 => Int32Type,
+    i64 => Int64Type,
+    u8 => UInt8Type,
+    u16 => UInt16Type,
+    u32 => UInt32Type,
+    u64 => UInt64Type
+);
+
+impl<T> NamedArray<T>
+where
+    T: ArrowNative,
+{
+    pub fn from_arrow(name: &str, array: ArrayRef) -> Result<NamedArray<T>> {
+        let expected = T::ArrowType::DATA_TYPE;
+        if array.data_type() != &expected {
+            return Err(ValidationError(ErrString::from(format!(
+                "column {name} has type {}, expected {expected}",
+                array.data_type()
+            ))));
+        }
+        if array.null_count() > 0 {
+            return Err(ValidationError(ErrString::from(format!(
+                "column {name} contains null values"
+            ))));
+        }
+
+        // dropping the ArrayRef leaves the downcast array as the only owner of
+        // the values buffer, which lets `into_vec` hand it over without a copy
+        let primitive: PrimitiveArray<T::ArrowType> = downcast_array(array.as_ref());
+        drop(array);
+        let (_, values, _) = primitive.into_parts();
+        let data = match values.into_inner().into_vec::<T>() {
+            Ok(data) => data,
+            Err(buffer) => buffer.typed_data::<T>().to_vec(),
+        };
+
+        NamedArray::new(name, data)
+    }
+
+    pub fn into_arrow(self) -> ArrayRef {
+        let values = ScalarBuffer::from(self.data);
+        Arc::new(PrimitiveArray::<T::ArrowType>::new(values, None))
+    }
+
+    pub fn arrow_field(&self) -> Field {
+        Field::new(self.name.clone(), T::ArrowType::DATA_TYPE, false)
+    }
+}
+
+pub fn to_record_batch<T>(columns: Vec<NamedArray<T>>) -> Result<RecordBatch>
+where
+    T: ArrowNative,
+{
+    let fields: Vec<Field> = columns.iter().map(|nm| nm.arrow_field()).collect();
+    let arrays: Vec<ArrayRef> = columns.into_iter().map(|nm| nm.into_arrow()).collect();
+    match RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays) {
+        Ok(batch) => Ok(batch),
+        Err(e) => Err(ValidationError(ErrString::from(e.to_string()))),
+    }
+}
+
+pub fn from_record_batch<T>(mut batch: RecordBatch) -> Result<Vec<NamedArray<T>>>
+where
+    T: ArrowNative,
+{
+    let schema = batch.schema();
+    let mut columns: Vec<NamedArray<T>> = Vec::new();
+    for field in schema.fields().iter() {
+        let array = batch.remove_column(0);
+        columns.push(NamedArray::from_arrow(field.name(), array)?);
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Float64Array, Int32Array};
+    use rstest::*;
+
+    #[fixture]
+    fn columns_fixture() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "age".to_string(),
+                data: vec![0.061696, -0.051474, 0.044451, -0.011595, -0.036385],
+            },
+            NamedArray {
+                name: "target".to_string(),
+                data: vec![151.0, 75.0, 141.0, 206.0, 135.0],
+            },
+        ]
+    }
+
+    #[rstest]
+    fn test_round_trip(columns_fixture: Vec<NamedArray<f64>>) {
+        let batch = to_record_batch(columns_fixture.clone()).unwrap();
+        assert_eq!(batch.num_columns(), 2);
+        assert_eq!(batch.num_rows(), 5);
+        assert_eq!(batch.schema().field(0).name(), "age");
+
+        let res: Vec<NamedArray<f64>> = from_record_batch(batch).unwrap();
+        assert_eq!(res, columns_fixture);
+    }
+
+    #[rstest]
+    fn test_zero_copy(columns_fixture: Vec<NamedArray<f64>>) {
+        let named_array = columns_fixture[0].clone();
+        let ptr = named_array.data.as_ptr();
+
+        let array = named_array.into_arrow();
+        let res: NamedArray<f64> = NamedArray::from_arrow("age", array).unwrap();
+        assert_eq!(res.data.as_ptr(), ptr);
+    }
+
+    #[rstest]
+    fn test_sliced_array_is_copied() {
+        let array: ArrayRef = Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0, 4.0]).slice(1, 2));
+        let res: NamedArray<f64> = NamedArray::from_arrow("x", array).unwrap();
+        assert_eq!(res.data, vec![2.0, 3.0]);
+    }
+
+    #[rstest]
+    fn test_integer_column() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let res: NamedArray<i32> = NamedArray::from_arrow("x", array).unwrap();
+        assert_eq!(res.data, vec![1, 2, 3]);
+    }
+
+    #[rstest]
+    fn test_wrong_type() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        assert!(NamedArray::<f64>::from_arrow("x", array).is_err());
+    }
+
+    #[rstest]
+    fn test_nulls() {
+        let array: ArrayRef = Arc::new(Float64Array::from(vec![Some(1.0), None, Some(3.0)]));
+        assert!(NamedArray::<f64>::from_arrow("x", array).is_err());
+    }
+
+    #[rstest]
+    fn test_mismatched_lengths() {
+        let columns = vec![
+            NamedArray {
+                name: "x".to_string(),
+                data: vec![1.0, 2.0, 3.0],
+            },
+            NamedArray {
+                name: "y".to_string(),
+                data: vec![1.0, 2.0],
+            },
+        ];
+        assert!(to_record_batch(columns).is_err());
+    }
+}
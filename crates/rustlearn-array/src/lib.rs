@@ -1 +1,9 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod namedarray;
+pub mod ndarray;
+pub mod ops;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod sparse;
+pub mod stats;
@@ -1,5 +1,6 @@
 use num::{Num, ToPrimitive};
-use rustlearn_errors::RustLearnError;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct NamedArray<T> {
@@ -32,6 +33,9 @@ where
     where
         T: Into<f64>,
     {
+        if self.is_empty() {
+            return Err(ValidationError(ErrString::from("array is empty")));
+        }
         let sum: T = self.data.iter().copied().fold(T::zero(), T::add);
         let sum_f64: f64 = sum.into();
         let len: f64 = self.data.len() as f64;
@@ -86,6 +90,12 @@ mod array_tests {
         assert_eq!(val, 2.5)
     }
 
+    #[rstest]
+    fn test_mean_empty() {
+        let empty: NamedArray<f64> = NamedArray::new("y", Vec::new()).unwrap();
+        assert!(empty.mean().is_err());
+    }
+
     #[rstest]
     fn test_dot_product(named_array_fixture: NamedArray<f64>) {
         let ans = named_array_fixture
@@ -0,0 +1,153 @@
+use ::ndarray::{Array1, Array2, ArrayView1, ShapeBuilder};
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+use crate::namedarray::{NamedArray, Result};
+
+impl<T> NamedArray<T>
+where
+    T: Clone,
+{
+    pub fn from_array1(name: &str, array: Array1<T>) -> NamedArray<T> {
+        let len = array.len();
+        let data = if array.is_standard_layout() {
+            match array.into_raw_vec_and_offset() {
+                (data, Some(0) | None) if data.len() == len => data,
+                (data, offset) => {
+                    let offset = offset.unwrap_or(0);
+                    data[offset..offset + len].to_vec()
+                }
+            }
+        } else {
+            array.to_vec()
+        };
+        NamedArray {
+            name: name.to_owned(),
+            data,
+        }
+    }
+
+    pub fn view(&self) -> ArrayView1<'_, T> {
+        ArrayView1::from(&self.data)
+    }
+}
+
+impl<T> From<NamedArray<T>> for Array1<T> {
+    fn from(named_array: NamedArray<T>) -> Self {
+        Array1::from(named_array.data)
+    }
+}
+
+pub fn to_array2<T>(columns: &[NamedArray<T>]) -> Result<(Array2<T>, Vec<String>)>
+where
+    T: Clone,
+{
+    if columns.is_empty() {
+        return Err(ValidationError(ErrString::from("no columns provided")));
+    }
+    let n_rows = columns[0].data.len();
+    let mut names: Vec<String> = Vec::new();
+    let mut values: Vec<T> = Vec::with_capacity(n_rows * columns.len());
+    for col in columns.iter() {
+        if col.data.len() != n_rows {
+            return Err(ValidationError(ErrString::from("mismatch column lengths")));
+        }
+        names.push(col.name.clone());
+        values.extend_from_slice(&col.data);
+    }
+
+    match Array2::from_shape_vec((n_rows, columns.len()).f(), values) {
+        Ok(matrix) => Ok((matrix, names)),
+        Err(e) => Err(ValidationError(ErrString::from(e.to_string()))),
+    }
+}
+
+pub fn from_array2<T>(matrix: Array2<T>, names: &[String]) -> Result<Vec<NamedArray<T>>>
+where
+    T: Clone,
+{
+    if matrix.ncols() != names.len() {
+        return Err(ValidationError(ErrString::from(
+            "number of names does not match number of columns",
+        )));
+    }
+    Ok(matrix
+        .columns()
+        .into_iter()
+        .zip(names.iter())
+        .map(|(col, name)| NamedArray {
+            name: name.clone(),
+            data: col.to_vec(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::ndarray::{array, s};
+    use rstest::*;
+
+    #[fixture]
+    fn columns_fixture() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "age".to_string(),
+                data: vec![1.0, 2.0, 3.0],
+            },
+            NamedArray {
+                name: "bmi".to_string(),
+                data: vec![4.0, 5.0, 6.0],
+            },
+        ]
+    }
+
+    #[rstest]
+    fn test_to_array2(columns_fixture: Vec<NamedArray<f64>>) {
+        let (matrix, names) = to_array2(&columns_fixture).unwrap();
+        assert_eq!(matrix, array![[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+        assert_eq!(names, vec!["age".to_string(), "bmi".to_string()]);
+    }
+
+    #[rstest]
+    fn test_round_trip(columns_fixture: Vec<NamedArray<f64>>) {
+        let (matrix, names) = to_array2(&columns_fixture).unwrap();
+        assert_eq!(from_array2(matrix, &names).unwrap(), columns_fixture);
+    }
+
+    #[rstest]
+    fn test_to_array2_empty() {
+        assert!(to_array2::<f64>(&[]).is_err());
+    }
+
+    #[rstest]
+    fn test_to_array2_mismatch(mut columns_fixture: Vec<NamedArray<f64>>) {
+        columns_fixture[1].data.pop();
+        assert!(to_array2(&columns_fixture).is_err());
+    }
+
+    #[rstest]
+    fn test_from_array2_wrong_names() {
+        let matrix = array![[1.0, 4.0], [2.0, 5.0]];
+        assert!(from_array2(matrix, &["x".to_string()]).is_err());
+    }
+
+    #[rstest]
+    fn test_array1_round_trip(columns_fixture: Vec<NamedArray<f64>>) {
+        let named_array = columns_fixture[0].clone();
+        let array: Array1<f64> = named_array.clone().into();
+        assert_eq!(array, array![1.0, 2.0, 3.0]);
+        assert_eq!(NamedArray::from_array1("age", array), named_array);
+    }
+
+    #[rstest]
+    fn test_from_array1_sliced() {
+        let array = array![1.0, 2.0, 3.0, 4.0].slice_move(s![1..3]);
+        let res = NamedArray::from_array1("x", array);
+        assert_eq!(res.data, vec![2.0, 3.0]);
+
+        let strided = array![1.0, 2.0, 3.0, 4.0].slice_move(s![..;2]);
+        let res = NamedArray::from_array1("x", strided);
+        assert_eq!(res.data, vec![1.0, 3.0]);
+    }
+}
@@ -0,0 +1,472 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use num::{Num, Signed, ToPrimitive};
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+use crate::namedarray::{NamedArray, Result};
+
+// integer arithmetic panics on overflow in debug builds and wraps in release
+// builds, so the operators go through the checked versions and return an
+// error instead; floats overflow to infinity and never fail
+pub trait CheckedOps: Sized {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_int {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedOps for $t {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    <$t>::checked_div(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_checked_float {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedOps for $t {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other)
+                }
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    Some(self * other)
+                }
+                fn checked_div(self, other: Self) -> Option<Self> {
+                    Some(self / other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_checked_float!(f32, f64);
+
+fn overflow_checks<T>(checked: NamedArray<Option<T>>) -> Result<NamedArray<T>> {
+    let mut data: Vec<T> = Vec::with_capacity(checked.data.len());
+    for value in checked.data {
+        match value {
+            Some(v) => data.push(v),
+            None => return Err(ValidationError(ErrString::from("integer overflow"))),
+        }
+    }
+    Ok(NamedArray {
+        name: checked.name,
+        data,
+    })
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $checked:ident, $symbol:literal) => {
+        impl<T> $trait<&NamedArray<T>> for &NamedArray<T>
+        where
+            T: Num + Copy + CheckedOps,
+        {
+            type Output = Result<NamedArray<T>>;
+
+            fn $method(self, other: &NamedArray<T>) -> Self::Output {
+                let name = format!("{} {} {}", self.name, $symbol, other.name);
+                overflow_checks(self.zip_with(other, &name, |a, b| a.$checked(b))?)
+            }
+        }
+
+        impl<T> $trait<NamedArray<T>> for NamedArray<T>
+        where
+            T: Num + Copy + CheckedOps,
+        {
+            type Output = Result<NamedArray<T>>;
+
+            fn $method(self, other: NamedArray<T>) -> Self::Output {
+                (&self).$method(&other)
+            }
+        }
+
+        impl<T> $trait<T> for &NamedArray<T>
+        where
+            T: Num + Copy + CheckedOps + ToString,
+        {
+            type Output = Result<NamedArray<T>>;
+
+            fn $method(self, scalar: T) -> Self::Output {
+                let name = format!("{} {} {}", self.name, $symbol, scalar.to_string());
+                overflow_checks(self.map(&name, |a| a.$checked(scalar)))
+            }
+        }
+
+        impl<T> $trait<T> for NamedArray<T>
+        where
+            T: Num + Copy + CheckedOps + ToString,
+        {
+            type Output = Result<NamedArray<T>>;
+
+            fn $method(self, scalar: T) -> Self::Output {
+                (&self).$method(scalar)
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, checked_add, "+");
+impl_binary_op!(Sub, sub, checked_sub, "-");
+impl_binary_op!(Mul, mul, checked_mul, "*");
+
+// dividing by zero panics for integer types, so any zero divisor is an error
+// for floats too; the minimum of a signed type divided by -1 overflows
+impl<T> Div<&NamedArray<T>> for &NamedArray<T>
+where
+    T: Num + Copy + CheckedOps,
+{
+    type Output = Result<NamedArray<T>>;
+
+    fn div(self, other: &NamedArray<T>) -> Self::Output {
+        if other.data.iter().any(|x| x.is_zero()) {
+            return Err(ValidationError(ErrString::from("division by zero")));
+        }
+        let name = format!("{} / {}", self.name, other.name);
+        overflow_checks(self.zip_with(other, &name, |a, b| a.checked_div(b))?)
+    }
+}
+
+impl<T> Div<NamedArray<T>> for NamedArray<T>
+where
+    T: Num + Copy + CheckedOps,
+{
+    type Output = Result<NamedArray<T>>;
+
+    fn div(self, other: NamedArray<T>) -> Self::Output {
+        &self / &other
+    }
+}
+
+impl<T> Div<T> for &NamedArray<T>
+where
+    T: Num + Copy + CheckedOps + ToString,
+{
+    type Output = Result<NamedArray<T>>;
+
+    fn div(self, scalar: T) -> Self::Output {
+        if scalar.is_zero() {
+            return Err(ValidationError(ErrString::from("division by zero")));
+        }
+        let name = format!("{} / {}", self.name, scalar.to_string());
+        overflow_checks(self.map(&name, |a| a.checked_div(scalar)))
+    }
+}
+
+impl<T> Div<T> for NamedArray<T>
+where
+    T: Num + Copy + CheckedOps + ToString,
+{
+    type Output = Result<NamedArray<T>>;
+
+    fn div(self, scalar: T) -> Self::Output {
+        &self / scalar
+    }
+}
+
+impl<T> NamedArray<T>
+where
+    T: Num + Copy,
+{
+    pub fn map<U, F>(&self, name: &str, f: F) -> NamedArray<U>
+    where
+        F: Fn(T) -> U,
+    {
+        NamedArray {
+            name: name.to_owned(),
+            data: self.data.iter().map(|x| f(*x)).collect(),
+        }
+    }
+
+    pub fn zip_with<U, F>(&self, other: &NamedArray<T>, name: &str, f: F) -> Result<NamedArray<U>>
+    where
+        F: Fn(T, T) -> U,
+    {
+        if self.len() != other.len() {
+            return Err(ValidationError(ErrString::from(
+                "series are not the same length",
+            )));
+        }
+        Ok(NamedArray {
+            name: name.to_owned(),
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+        })
+    }
+
+    pub fn cumsum(&self) -> Result<NamedArray<T>>
+    where
+        T: CheckedOps,
+    {
+        let mut total = T::zero();
+        let mut data: Vec<T> = Vec::with_capacity(self.data.len());
+        for x in self.data.iter() {
+            total = match total.checked_add(*x) {
+                Some(t) => t,
+                None => return Err(ValidationError(ErrString::from("integer overflow"))),
+            };
+            data.push(total);
+        }
+        Ok(NamedArray {
+            name: format!("cumsum({})", self.name),
+            data,
+        })
+    }
+
+    // a decrease of an unsigned series overflows like any other difference
+    // that does not fit the type
+    pub fn diff(&self) -> Result<NamedArray<T>>
+    where
+        T: CheckedOps,
+    {
+        overflow_checks(NamedArray {
+            name: format!("diff({})", self.name),
+            data: self
+                .data
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect(),
+        })
+    }
+
+    pub fn abs(&self) -> NamedArray<T>
+    where
+        T: Signed,
+    {
+        self.map(&format!("abs({})", self.name), |x| x.abs())
+    }
+
+    pub fn log(&self) -> NamedArray<f64>
+    where
+        T: ToPrimitive,
+    {
+        self.map(&format!("log({})", self.name), |x| x.to_f64().unwrap().ln())
+    }
+
+    pub fn exp(&self) -> NamedArray<f64>
+    where
+        T: ToPrimitive,
+    {
+        self.map(&format!("exp({})", self.name), |x| {
+            x.to_f64().unwrap().exp()
+        })
+    }
+
+    pub fn clip(&self, min: T, max: T) -> Result<NamedArray<T>>
+    where
+        T: PartialOrd,
+    {
+        if min > max {
+            return Err(ValidationError(ErrString::from(
+                "clip minimum is larger than the maximum",
+            )));
+        }
+        Ok(self.map(&format!("clip({})", self.name), |x| {
+            if x < min {
+                min
+            } else if x > max {
+                max
+            } else {
+                x
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn age() -> NamedArray<f64> {
+        NamedArray {
+            name: "age".to_string(),
+            data: vec![1.0, 2.0, 4.0],
+        }
+    }
+
+    #[fixture]
+    fn bmi() -> NamedArray<f64> {
+        NamedArray {
+            name: "bmi".to_string(),
+            data: vec![2.0, -3.0, 8.0],
+        }
+    }
+
+    #[rstest]
+    fn test_array_ops(age: NamedArray<f64>, bmi: NamedArray<f64>) {
+        let res = (&age + &bmi).unwrap();
+        assert_eq!(res.name, "age + bmi");
+        assert_eq!(res.data, vec![3.0, -1.0, 12.0]);
+
+        assert_eq!((&age - &bmi).unwrap().data, vec![-1.0, 5.0, -4.0]);
+        assert_eq!((&age * &bmi).unwrap().data, vec![2.0, -6.0, 32.0]);
+        assert_eq!((age / bmi).unwrap().data, vec![0.5, -2.0 / 3.0, 0.5]);
+    }
+
+    #[rstest]
+    fn test_array_ops_mismatch(age: NamedArray<f64>) {
+        let short = NamedArray {
+            name: "short".to_string(),
+            data: vec![1.0],
+        };
+        assert!((&age + &short).is_err());
+        assert!((age / short).is_err());
+    }
+
+    #[rstest]
+    fn test_scalar_ops(age: NamedArray<f64>) {
+        let res = (&age * 2.0).unwrap();
+        assert_eq!(res.name, "age * 2");
+        assert_eq!(res.data, vec![2.0, 4.0, 8.0]);
+
+        assert_eq!((&age + 1.0).unwrap().data, vec![2.0, 3.0, 5.0]);
+        assert_eq!((&age - 1.0).unwrap().data, vec![0.0, 1.0, 3.0]);
+        assert_eq!((age / 2.0).unwrap().data, vec![0.5, 1.0, 2.0]);
+    }
+
+    #[rstest]
+    fn test_integer_ops() {
+        let x = NamedArray {
+            name: "x".to_string(),
+            data: vec![1, 2, 3],
+        };
+        assert_eq!((&x + &x).unwrap().data, vec![2, 4, 6]);
+        assert_eq!((x.clone() * 3).unwrap().data, vec![3, 6, 9]);
+        assert_eq!((&x / 2).unwrap().data, vec![0, 1, 1]);
+    }
+
+    #[rstest]
+    fn test_division_by_zero(age: NamedArray<f64>) {
+        let x = NamedArray {
+            name: "x".to_string(),
+            data: vec![4, 2, 3],
+        };
+        let zeros = NamedArray {
+            name: "zeros".to_string(),
+            data: vec![1, 0, 1],
+        };
+        assert!((&x / 0).is_err());
+        assert!((x / zeros).is_err());
+        assert!((age / 0.0).is_err());
+    }
+
+    #[rstest]
+    fn test_integer_overflow() {
+        let x: NamedArray<i32> = NamedArray {
+            name: "x".to_string(),
+            data: vec![1, i32::MAX],
+        };
+        let minimum: NamedArray<i32> = NamedArray {
+            name: "minimum".to_string(),
+            data: vec![i32::MIN],
+        };
+        assert!((&x + &x).is_err());
+        assert!((&x + 1).is_err());
+        assert!((&minimum - 1).is_err());
+        assert!((&x * 2).is_err());
+        assert!((&minimum / -1).is_err());
+        assert!(x.cumsum().is_err());
+        let jump: NamedArray<i32> = NamedArray {
+            name: "jump".to_string(),
+            data: vec![i32::MIN, i32::MAX],
+        };
+        assert!(jump.diff().is_err());
+
+        let large = NamedArray {
+            name: "large".to_string(),
+            data: vec![f64::MAX],
+        };
+        assert_eq!((large * 2.0).unwrap().data, vec![f64::INFINITY]);
+    }
+
+    #[rstest]
+    fn test_map_zip_with(age: NamedArray<f64>, bmi: NamedArray<f64>) {
+        let res = age.map("age_squared", |x| x * x);
+        assert_eq!(res.name, "age_squared");
+        assert_eq!(res.data, vec![1.0, 4.0, 16.0]);
+
+        let res = age.zip_with(&bmi, "max", |a, b| a.max(b)).unwrap();
+        assert_eq!(res.data, vec![2.0, 2.0, 8.0]);
+    }
+
+    #[rstest]
+    fn test_cumsum_diff(age: NamedArray<f64>) {
+        let res = age.cumsum().unwrap();
+        assert_eq!(res.name, "cumsum(age)");
+        assert_eq!(res.data, vec![1.0, 3.0, 7.0]);
+
+        let res = age.diff().unwrap();
+        assert_eq!(res.name, "diff(age)");
+        assert_eq!(res.data, vec![1.0, 2.0]);
+    }
+
+    #[rstest]
+    fn test_diff_unsigned() {
+        let increasing: NamedArray<u32> = NamedArray {
+            name: "x".to_string(),
+            data: vec![1, 3, 6],
+        };
+        assert_eq!(increasing.diff().unwrap().data, vec![2, 3]);
+        let decreasing: NamedArray<u32> = NamedArray {
+            name: "x".to_string(),
+            data: vec![3, 1],
+        };
+        assert!(decreasing.diff().is_err());
+        let signed = NamedArray {
+            name: "x".to_string(),
+            data: vec![3, 1],
+        };
+        assert_eq!(signed.diff().unwrap().data, vec![-2]);
+    }
+
+    #[rstest]
+    fn test_abs(bmi: NamedArray<f64>) {
+        let res = bmi.abs();
+        assert_eq!(res.name, "abs(bmi)");
+        assert_eq!(res.data, vec![2.0, 3.0, 8.0]);
+    }
+
+    #[rstest]
+    fn test_log_exp(age: NamedArray<f64>) {
+        let res = age.log();
+        assert_eq!(res.name, "log(age)");
+        assert_eq!(res.data, vec![0.0, 2.0_f64.ln(), 4.0_f64.ln()]);
+
+        let res = age.exp();
+        assert_eq!(res.name, "exp(age)");
+        assert_eq!(res.data, vec![1.0_f64.exp(), 2.0_f64.exp(), 4.0_f64.exp()]);
+    }
+
+    #[rstest]
+    fn test_clip(bmi: NamedArray<f64>) {
+        let res = bmi.clip(0.0, 5.0).unwrap();
+        assert_eq!(res.name, "clip(bmi)");
+        assert_eq!(res.data, vec![2.0, 0.0, 5.0]);
+        assert!(bmi.clip(5.0, 0.0).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs::File;
+use std::path::Path;
+
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use ::parquet::arrow::ArrowWriter;
+use arrow_array::ArrowPrimitiveType;
+use rustlearn_errors::RustLearnError::{IoError, ValidationError};
+use rustlearn_errors::{ErrString, RustLearnError};
+
+use crate::arrow::{from_record_batch, to_record_batch, ArrowNative};
+use crate::namedarray::{NamedArray, Result};
+
+fn io_error<E: ToString>(e: E) -> RustLearnError {
+    IoError(ErrString::from(e.to_string()))
+}
+
+pub fn read_parquet<T>(path: impl AsRef<Path>) -> Result<Vec<NamedArray<T>>>
+where
+    T: ArrowNative,
+{
+    let file = File::open(path).map_err(io_error)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(io_error)?;
+
+    let mut columns: Vec<NamedArray<T>> = Vec::new();
+    for field in builder.schema().fields().iter() {
+        if field.data_type() != &T::ArrowType::DATA_TYPE {
+            return Err(ValidationError(ErrString::from(format!(
+                "column {} has type {}, expected {}",
+                field.name(),
+                field.data_type(),
+                T::ArrowType::DATA_TYPE
+            ))));
+        }
+        columns.push(NamedArray::new(field.name(), Vec::new())?);
+    }
+
+    let reader = builder.build().map_err(io_error)?;
+    for batch in reader {
+        let batch = batch.map_err(io_error)?;
+        for (col, new) in columns.iter_mut().zip(from_record_batch::<T>(batch)?) {
+            if col.is_empty() {
+                col.data = new.data;
+            } else {
+                col.data.extend(new.data);
+            }
+        }
+    }
+    Ok(columns)
+}
+
+pub fn write_parquet<T>(path: impl AsRef<Path>, columns: Vec<NamedArray<T>>) -> Result<()>
+where
+    T: ArrowNative,
+{
+    let batch = to_record_batch(columns)?;
+    let file = File::create(path).map_err(io_error)?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None).map_err(io_error)?;
+    writer.write(&batch).map_err(io_error)?;
+    writer.close().map_err(io_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::path::PathBuf;
+
+    #[fixture]
+    fn columns_fixture() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "age".to_string(),
+                data: vec![0.061696, -0.051474, 0.044451, -0.011595, -0.036385],
+            },
+            NamedArray {
+                name: "target".to_string(),
+                data: vec![151.0, 75.0, 141.0, 206.0, 135.0],
+            },
+        ]
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rustlearn-{}-{name}", std::process::id()))
+    }
+
+    #[rstest]
+    fn test_round_trip(columns_fixture: Vec<NamedArray<f64>>) {
+        let path = temp_path("round_trip.parquet");
+        write_parquet(&path, columns_fixture.clone()).unwrap();
+        let res: Vec<NamedArray<f64>> = read_parquet(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(res, columns_fixture);
+    }
+
+    #[rstest]
+    fn test_wrong_type(columns_fixture: Vec<NamedArray<f64>>) {
+        let path = temp_path("wrong_type.parquet");
+        write_parquet(&path, columns_fixture).unwrap();
+        let res = read_parquet::<i64>(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(res.is_err());
+    }
+
+    #[rstest]
+    fn test_missing_file() {
+        let res = read_parquet::<f64>(temp_path("missing.parquet"));
+        assert!(matches!(res, Err(IoError(_))));
+    }
+}
@@ -0,0 +1,474 @@
+use num::{Num, ToPrimitive};
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+use crate::namedarray::{NamedArray, Result};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SparseNamedArray<T> {
+    pub name: String,
+    pub len: usize,
+    pub indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CscMatrix<T> {
+    pub names: Vec<String>,
+    pub n_rows: usize,
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub data: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CsrMatrix<T> {
+    pub names: Vec<String>,
+    pub n_rows: usize,
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub data: Vec<T>,
+}
+
+fn assert_sorted_indices(indices: &[usize], bound: usize) -> Result<()> {
+    for (i, idx) in indices.iter().enumerate() {
+        if *idx >= bound {
+            return Err(ValidationError(ErrString::from("index out of bounds")));
+        }
+        if i > 0 && indices[i - 1] >= *idx {
+            return Err(ValidationError(ErrString::from(
+                "indices must be strictly increasing",
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn assert_compressed(
+    n_major: usize,
+    n_minor: usize,
+    indptr: &[usize],
+    indices: &[usize],
+    n_data: usize,
+) -> Result<()> {
+    if indptr.len() != n_major + 1 || indptr[0] != 0 {
+        return Err(ValidationError(ErrString::from(
+            "indptr must start at 0 and have one entry per row or column plus one",
+        )));
+    }
+    if indices.len() != n_data || indptr[n_major] != n_data {
+        return Err(ValidationError(ErrString::from(
+            "indices and data must have indptr[-1] entries",
+        )));
+    }
+    for window in indptr.windows(2) {
+        if window[0] > window[1] || window[1] > n_data {
+            return Err(ValidationError(ErrString::from(
+                "indptr must be non-decreasing",
+            )));
+        }
+        assert_sorted_indices(&indices[window[0]..window[1]], n_minor)?;
+    }
+    Ok(())
+}
+
+fn transpose_compressed<T: Copy>(
+    n_minor: usize,
+    indptr: &[usize],
+    indices: &[usize],
+    data: &[T],
+) -> (Vec<usize>, Vec<usize>, Vec<T>) {
+    let mut counts = vec![0; n_minor + 1];
+    for idx in indices.iter() {
+        counts[*idx + 1] += 1;
+    }
+    for i in 0..n_minor {
+        counts[i + 1] += counts[i];
+    }
+    let new_indptr = counts.clone();
+
+    let mut new_indices = vec![0; indices.len()];
+    let mut new_data: Vec<T> = data.to_vec();
+    for major in 0..indptr.len() - 1 {
+        for pos in indptr[major]..indptr[major + 1] {
+            let minor = indices[pos];
+            new_indices[counts[minor]] = major;
+            new_data[counts[minor]] = data[pos];
+            counts[minor] += 1;
+        }
+    }
+    (new_indptr, new_indices, new_data)
+}
+
+impl<T> SparseNamedArray<T>
+where
+    T: Num + Copy,
+{
+    pub fn new(name: &str, len: usize, indices: Vec<usize>, values: Vec<T>) -> Result<Self> {
+        if indices.len() != values.len() {
+            return Err(ValidationError(ErrString::from(
+                "indices and values must be the same length",
+            )));
+        }
+        assert_sorted_indices(&indices, len)?;
+        Ok(SparseNamedArray {
+            name: name.to_owned(),
+            len,
+            indices,
+            values,
+        })
+    }
+
+    pub fn from_dense(named_array: &NamedArray<T>) -> Self {
+        let mut indices: Vec<usize> = Vec::new();
+        let mut values: Vec<T> = Vec::new();
+        for (i, x) in named_array.data.iter().enumerate() {
+            if !x.is_zero() {
+                indices.push(i);
+                values.push(*x);
+            }
+        }
+        SparseNamedArray {
+            name: named_array.name.clone(),
+            len: named_array.len(),
+            indices,
+            values,
+        }
+    }
+
+    pub fn to_dense(&self) -> NamedArray<T> {
+        let mut data = vec![T::zero(); self.len];
+        for (i, x) in self.indices.iter().zip(self.values.iter()) {
+            data[*i] = *x;
+        }
+        NamedArray {
+            name: self.name.clone(),
+            data,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn dot(&self, other: &NamedArray<T>) -> Result<f64>
+    where
+        T: ToPrimitive,
+    {
+        if self.len != other.len() {
+            return Err(ValidationError(ErrString::from(
+                "series are not the same length",
+            )));
+        }
+        let mut total: f64 = 0.0;
+        for (i, x) in self.indices.iter().zip(self.values.iter()) {
+            total += x.to_f64().unwrap() * other.data[*i].to_f64().unwrap();
+        }
+        Ok(total)
+    }
+}
+
+impl<T> CscMatrix<T>
+where
+    T: Num + Copy + ToPrimitive,
+{
+    pub fn new(
+        names: Vec<String>,
+        n_rows: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<T>,
+    ) -> Result<Self> {
+        assert_compressed(names.len(), n_rows, &indptr, &indices, data.len())?;
+        Ok(CscMatrix {
+            names,
+            n_rows,
+            indptr,
+            indices,
+            data,
+        })
+    }
+
+    pub fn from_columns(columns: Vec<SparseNamedArray<T>>) -> Result<Self> {
+        if columns.is_empty() {
+            return Err(ValidationError(ErrString::from("no columns provided")));
+        }
+        let n_rows = columns[0].len;
+        let mut names: Vec<String> = Vec::new();
+        let mut indptr: Vec<usize> = vec![0];
+        let mut indices: Vec<usize> = Vec::new();
+        let mut data: Vec<T> = Vec::new();
+        for col in columns {
+            if col.len != n_rows {
+                return Err(ValidationError(ErrString::from("mismatch column lengths")));
+            }
+            names.push(col.name);
+            indices.extend(col.indices);
+            data.extend(col.values);
+            indptr.push(data.len());
+        }
+        Ok(CscMatrix {
+            names,
+            n_rows,
+            indptr,
+            indices,
+            data,
+        })
+    }
+
+    pub fn from_dense(columns: &[NamedArray<T>]) -> Result<Self> {
+        Self::from_columns(columns.iter().map(SparseNamedArray::from_dense).collect())
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn column(&self, j: usize) -> SparseNamedArray<T> {
+        let (start, end) = (self.indptr[j], self.indptr[j + 1]);
+        SparseNamedArray {
+            name: self.names[j].clone(),
+            len: self.n_rows,
+            indices: self.indices[start..end].to_vec(),
+            values: self.data[start..end].to_vec(),
+        }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let (indptr, indices, data) =
+            transpose_compressed(self.n_rows, &self.indptr, &self.indices, &self.data);
+        CsrMatrix {
+            names: self.names.clone(),
+            n_rows: self.n_rows,
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    pub fn to_dense(&self) -> Vec<NamedArray<T>> {
+        (0..self.n_cols())
+            .map(|j| self.column(j).to_dense())
+            .collect()
+    }
+
+    pub fn mul_vec(&self, v: &[f64]) -> Result<Vec<f64>> {
+        if v.len() != self.n_cols() {
+            return Err(ValidationError(ErrString::from(
+                "vector length does not match number of columns",
+            )));
+        }
+        let mut out = vec![0.0; self.n_rows];
+        for (j, coef) in v.iter().enumerate() {
+            for pos in self.indptr[j]..self.indptr[j + 1] {
+                out[self.indices[pos]] += self.data[pos].to_f64().unwrap() * coef;
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn transpose_mul_vec(&self, v: &[f64]) -> Result<Vec<f64>> {
+        if v.len() != self.n_rows {
+            return Err(ValidationError(ErrString::from(
+                "vector length does not match number of rows",
+            )));
+        }
+        Ok((0..self.n_cols())
+            .map(|j| {
+                (self.indptr[j]..self.indptr[j + 1])
+                    .map(|pos| self.data[pos].to_f64().unwrap() * v[self.indices[pos]])
+                    .sum()
+            })
+            .collect())
+    }
+}
+
+impl<T> CsrMatrix<T>
+where
+    T: Num + Copy + ToPrimitive,
+{
+    pub fn new(
+        names: Vec<String>,
+        n_rows: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        data: Vec<T>,
+    ) -> Result<Self> {
+        assert_compressed(n_rows, names.len(), &indptr, &indices, data.len())?;
+        Ok(CsrMatrix {
+            names,
+            n_rows,
+            indptr,
+            indices,
+            data,
+        })
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let (indptr, indices, data) =
+            transpose_compressed(self.n_cols(), &self.indptr, &self.indices, &self.data);
+        CscMatrix {
+            names: self.names.clone(),
+            n_rows: self.n_rows,
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    pub fn mul_vec(&self, v: &[f64]) -> Result<Vec<f64>> {
+        if v.len() != self.n_cols() {
+            return Err(ValidationError(ErrString::from(
+                "vector length does not match number of columns",
+            )));
+        }
+        Ok((0..self.n_rows)
+            .map(|i| {
+                (self.indptr[i]..self.indptr[i + 1])
+                    .map(|pos| self.data[pos].to_f64().unwrap() * v[self.indices[pos]])
+                    .sum()
+            })
+            .collect())
+    }
+
+    pub fn transpose_mul_vec(&self, v: &[f64]) -> Result<Vec<f64>> {
+        if v.len() != self.n_rows {
+            return Err(ValidationError(ErrString::from(
+                "vector length does not match number of rows",
+            )));
+        }
+        let mut out = vec![0.0; self.n_cols()];
+        for (i, coef) in v.iter().enumerate() {
+            for pos in self.indptr[i]..self.indptr[i + 1] {
+                out[self.indices[pos]] += self.data[pos].to_f64().unwrap() * coef;
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn dense_columns() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "a".to_string(),
+                data: vec![1.0, 0.0, 0.0, 2.0],
+            },
+            NamedArray {
+                name: "b".to_string(),
+                data: vec![0.0, 0.0, 3.0, 0.0],
+            },
+            NamedArray {
+                name: "c".to_string(),
+                data: vec![0.0, 4.0, 5.0, 0.0],
+            },
+        ]
+    }
+
+    #[rstest]
+    fn test_sparse_round_trip(dense_columns: Vec<NamedArray<f64>>) {
+        let sparse = SparseNamedArray::from_dense(&dense_columns[0]);
+        assert_eq!(sparse.indices, vec![0, 3]);
+        assert_eq!(sparse.values, vec![1.0, 2.0]);
+        assert_eq!(sparse.nnz(), 2);
+        assert_eq!(sparse.len(), 4);
+        assert_eq!(sparse.to_dense(), dense_columns[0]);
+    }
+
+    #[rstest]
+    fn test_sparse_validation() {
+        assert!(SparseNamedArray::new("x", 3, vec![0, 3], vec![1.0, 2.0]).is_err());
+        assert!(SparseNamedArray::new("x", 3, vec![1, 0], vec![1.0, 2.0]).is_err());
+        assert!(SparseNamedArray::new("x", 3, vec![0], vec![1.0, 2.0]).is_err());
+        assert!(SparseNamedArray::new("x", 3, vec![0, 2], vec![1.0, 2.0]).is_ok());
+    }
+
+    #[rstest]
+    fn test_sparse_dot(dense_columns: Vec<NamedArray<f64>>) {
+        let sparse = SparseNamedArray::from_dense(&dense_columns[2]);
+        assert_eq!(sparse.dot(&dense_columns[2]).unwrap(), 41.0);
+        let short = NamedArray {
+            name: "short".to_string(),
+            data: vec![1.0],
+        };
+        assert!(sparse.dot(&short).is_err());
+    }
+
+    #[rstest]
+    fn test_csc_from_dense(dense_columns: Vec<NamedArray<f64>>) {
+        let csc = CscMatrix::from_dense(&dense_columns).unwrap();
+        assert_eq!(csc.indptr, vec![0, 2, 3, 5]);
+        assert_eq!(csc.indices, vec![0, 3, 2, 1, 2]);
+        assert_eq!(csc.data, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(csc.nnz(), 5);
+        assert_eq!(csc.to_dense(), dense_columns);
+    }
+
+    #[rstest]
+    fn test_csr_csc_round_trip(dense_columns: Vec<NamedArray<f64>>) {
+        let csc = CscMatrix::from_dense(&dense_columns).unwrap();
+        let csr = csc.to_csr();
+        assert_eq!(csr.indptr, vec![0, 1, 2, 4, 5]);
+        assert_eq!(csr.indices, vec![0, 2, 1, 2, 0]);
+        assert_eq!(csr.data, vec![1.0, 4.0, 3.0, 5.0, 2.0]);
+        assert_eq!(csr.to_csc(), csc);
+    }
+
+    #[rstest]
+    fn test_matrix_validation() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        assert!(
+            CsrMatrix::new(names.clone(), 2, vec![0, 5, 2], vec![0, 1], vec![1.0, 2.0]).is_err()
+        );
+        assert!(
+            CsrMatrix::new(names.clone(), 2, vec![0, 1, 2], vec![0, 1], vec![1.0, 2.0]).is_ok()
+        );
+        assert!(CsrMatrix::new(names.clone(), 2, vec![0, 1], vec![0], vec![1.0]).is_err());
+        assert!(
+            CsrMatrix::new(names.clone(), 2, vec![0, 1, 2], vec![0, 2], vec![1.0, 2.0]).is_err()
+        );
+        assert!(CscMatrix::new(names, 2, vec![0, 2, 1], vec![0, 1], vec![1.0, 2.0]).is_err());
+    }
+
+    #[rstest]
+    fn test_mul_vec(dense_columns: Vec<NamedArray<f64>>) {
+        let csc = CscMatrix::from_dense(&dense_columns).unwrap();
+        let csr = csc.to_csr();
+        let v = vec![1.0, 2.0, 3.0];
+        let expected = vec![1.0, 12.0, 21.0, 2.0];
+        assert_eq!(csc.mul_vec(&v).unwrap(), expected);
+        assert_eq!(csr.mul_vec(&v).unwrap(), expected);
+        assert!(csr.mul_vec(&[1.0]).is_err());
+
+        let w = vec![1.0, 1.0, 1.0, 1.0];
+        let expected = vec![3.0, 3.0, 9.0];
+        assert_eq!(csc.transpose_mul_vec(&w).unwrap(), expected);
+        assert_eq!(csr.transpose_mul_vec(&w).unwrap(), expected);
+        assert!(csc.transpose_mul_vec(&[1.0]).is_err());
+    }
+}
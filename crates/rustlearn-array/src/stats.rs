@@ -0,0 +1,405 @@
+use num::{Num, ToPrimitive};
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+use crate::namedarray::{NamedArray, Result};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum QuantileMethod {
+    Linear,
+    Lower,
+    Higher,
+    Nearest,
+    Midpoint,
+}
+
+fn quantile_sorted(sorted: &[f64], q: f64, method: QuantileMethod) -> Result<f64> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(ValidationError(ErrString::from(
+            "quantile must be between 0 and 1",
+        )));
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+
+    let value = match method {
+        QuantileMethod::Linear => sorted[lower] + (sorted[upper] - sorted[lower]) * fraction,
+        QuantileMethod::Lower => sorted[lower],
+        QuantileMethod::Higher => sorted[upper],
+        QuantileMethod::Nearest => {
+            // ties go to the even index, matching numpy
+            if fraction < 0.5 || (fraction == 0.5 && lower.is_multiple_of(2)) {
+                sorted[lower]
+            } else {
+                sorted[upper]
+            }
+        }
+        QuantileMethod::Midpoint => (sorted[lower] + sorted[upper]) / 2.0,
+    };
+    Ok(value)
+}
+
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let average = (start + end) as f64 / 2.0 + 1.0;
+        for idx in order[start..=end].iter() {
+            ranks[*idx] = average;
+        }
+        start = end + 1;
+    }
+    ranks
+}
+
+impl<T> NamedArray<T>
+where
+    T: Num + Copy + ToPrimitive,
+{
+    fn assert_not_empty(&self) -> Result<()> {
+        if self.is_empty() {
+            return Err(ValidationError(ErrString::from(format!(
+                "array {} is empty",
+                self.name
+            ))));
+        }
+        Ok(())
+    }
+
+    fn assert_same_length(&self, other: &NamedArray<T>) -> Result<()> {
+        if self.len() != other.len() {
+            return Err(ValidationError(ErrString::from(
+                "series are not the same length",
+            )));
+        }
+        Ok(())
+    }
+
+    fn to_f64_vec(&self) -> Vec<f64> {
+        self.data.iter().map(|x| x.to_f64().unwrap()).collect()
+    }
+
+    fn central_moment(&self, order: i32) -> Result<f64> {
+        self.assert_not_empty()?;
+        let values = self.to_f64_vec();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let total: f64 = values.iter().map(|x| (x - mean).powi(order)).sum();
+        Ok(total / values.len() as f64)
+    }
+
+    pub fn sum(&self) -> Result<f64> {
+        self.assert_not_empty()?;
+        Ok(self.to_f64_vec().iter().sum())
+    }
+
+    pub fn var(&self, ddof: usize) -> Result<f64> {
+        self.assert_not_empty()?;
+        if self.len() <= ddof {
+            return Err(ValidationError(ErrString::from(
+                "ddof must be smaller than the array length",
+            )));
+        }
+        let m_2 = self.central_moment(2)?;
+        Ok(m_2 * self.len() as f64 / (self.len() - ddof) as f64)
+    }
+
+    pub fn std(&self, ddof: usize) -> Result<f64> {
+        Ok(self.var(ddof)?.sqrt())
+    }
+
+    pub fn min(&self) -> Result<T>
+    where
+        T: PartialOrd,
+    {
+        Ok(self.data[self.argmin()?])
+    }
+
+    pub fn max(&self) -> Result<T>
+    where
+        T: PartialOrd,
+    {
+        Ok(self.data[self.argmax()?])
+    }
+
+    pub fn argmin(&self) -> Result<usize>
+    where
+        T: PartialOrd,
+    {
+        self.assert_not_empty()?;
+        let mut idx = 0;
+        for (i, x) in self.data.iter().enumerate() {
+            if *x < self.data[idx] {
+                idx = i;
+            }
+        }
+        Ok(idx)
+    }
+
+    pub fn argmax(&self) -> Result<usize>
+    where
+        T: PartialOrd,
+    {
+        self.assert_not_empty()?;
+        let mut idx = 0;
+        for (i, x) in self.data.iter().enumerate() {
+            if *x > self.data[idx] {
+                idx = i;
+            }
+        }
+        Ok(idx)
+    }
+
+    pub fn median(&self) -> Result<f64> {
+        self.quantile(0.5, QuantileMethod::Linear)
+    }
+
+    pub fn quantile(&self, q: f64, method: QuantileMethod) -> Result<f64> {
+        Ok(self.quantiles(&[q], method)?[0])
+    }
+
+    pub fn quantiles(&self, qs: &[f64], method: QuantileMethod) -> Result<Vec<f64>> {
+        self.assert_not_empty()?;
+        let mut sorted = self.to_f64_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        qs.iter()
+            .map(|q| quantile_sorted(&sorted, *q, method))
+            .collect()
+    }
+
+    pub fn skewness(&self) -> Result<f64> {
+        let m_2 = self.central_moment(2)?;
+        if m_2 == 0.0 {
+            return Err(ValidationError(ErrString::from(
+                "skewness is undefined for a constant array",
+            )));
+        }
+        Ok(self.central_moment(3)? / m_2.powf(1.5))
+    }
+
+    pub fn kurtosis(&self) -> Result<f64> {
+        let m_2 = self.central_moment(2)?;
+        if m_2 == 0.0 {
+            return Err(ValidationError(ErrString::from(
+                "kurtosis is undefined for a constant array",
+            )));
+        }
+        Ok(self.central_moment(4)? / m_2.powi(2) - 3.0)
+    }
+
+    pub fn cov(&self, other: &NamedArray<T>, ddof: usize) -> Result<f64> {
+        self.assert_not_empty()?;
+        self.assert_same_length(other)?;
+        if self.len() <= ddof {
+            return Err(ValidationError(ErrString::from(
+                "ddof must be smaller than the array length",
+            )));
+        }
+        let x = self.to_f64_vec();
+        let y = other.to_f64_vec();
+        let n = x.len() as f64;
+        let m_x = x.iter().sum::<f64>() / n;
+        let m_y = y.iter().sum::<f64>() / n;
+        let total: f64 = x
+            .iter()
+            .zip(y.iter())
+            .map(|(a, b)| (a - m_x) * (b - m_y))
+            .sum();
+        Ok(total / (self.len() - ddof) as f64)
+    }
+
+    pub fn pearson(&self, other: &NamedArray<T>) -> Result<f64> {
+        self.assert_not_empty()?;
+        self.assert_same_length(other)?;
+        pearson(&self.to_f64_vec(), &other.to_f64_vec())
+    }
+
+    pub fn spearman(&self, other: &NamedArray<T>) -> Result<f64> {
+        self.assert_not_empty()?;
+        self.assert_same_length(other)?;
+        pearson(&ranks(&self.to_f64_vec()), &ranks(&other.to_f64_vec()))
+    }
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> Result<f64> {
+    let n = x.len() as f64;
+    let m_x = x.iter().sum::<f64>() / n;
+    let m_y = y.iter().sum::<f64>() / n;
+    let mut ss_xy = 0.0;
+    let mut ss_xx = 0.0;
+    let mut ss_yy = 0.0;
+    for (a, b) in x.iter().zip(y.iter()) {
+        ss_xy += (a - m_x) * (b - m_y);
+        ss_xx += (a - m_x).powi(2);
+        ss_yy += (b - m_y).powi(2);
+    }
+    if ss_xx == 0.0 || ss_yy == 0.0 {
+        return Err(ValidationError(ErrString::from(
+            "correlation is undefined for a constant array",
+        )));
+    }
+    Ok(ss_xy / (ss_xx * ss_yy).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    #[fixture]
+    fn named_array_fixture() -> NamedArray<f64> {
+        NamedArray {
+            name: "y".to_string(),
+            data: vec![2.0, 8.0, 1.0, 4.0, 10.0],
+        }
+    }
+
+    #[fixture]
+    fn empty_fixture() -> NamedArray<f64> {
+        NamedArray {
+            name: "empty".to_string(),
+            data: Vec::new(),
+        }
+    }
+
+    #[rstest]
+    fn test_empty_errors(empty_fixture: NamedArray<f64>) {
+        assert!(empty_fixture.sum().is_err());
+        assert!(empty_fixture.var(0).is_err());
+        assert!(empty_fixture.std(0).is_err());
+        assert!(empty_fixture.min().is_err());
+        assert!(empty_fixture.max().is_err());
+        assert!(empty_fixture.argmin().is_err());
+        assert!(empty_fixture.argmax().is_err());
+        assert!(empty_fixture.median().is_err());
+        assert!(empty_fixture.skewness().is_err());
+        assert!(empty_fixture.kurtosis().is_err());
+        assert!(empty_fixture.cov(&empty_fixture, 0).is_err());
+        assert!(empty_fixture.pearson(&empty_fixture).is_err());
+        assert!(empty_fixture.spearman(&empty_fixture).is_err());
+    }
+
+    #[rstest]
+    fn test_sum(named_array_fixture: NamedArray<f64>) {
+        assert_eq!(named_array_fixture.sum().unwrap(), 25.0);
+    }
+
+    #[rstest]
+    fn test_var_std(named_array_fixture: NamedArray<f64>) {
+        assert_close(named_array_fixture.var(0).unwrap(), 12.0);
+        assert_close(named_array_fixture.var(1).unwrap(), 15.0);
+        assert_close(named_array_fixture.std(1).unwrap(), 15.0_f64.sqrt());
+        assert!(named_array_fixture.var(5).is_err());
+    }
+
+    #[rstest]
+    fn test_min_max(named_array_fixture: NamedArray<f64>) {
+        assert_eq!(named_array_fixture.min().unwrap(), 1.0);
+        assert_eq!(named_array_fixture.max().unwrap(), 10.0);
+        assert_eq!(named_array_fixture.argmin().unwrap(), 2);
+        assert_eq!(named_array_fixture.argmax().unwrap(), 4);
+    }
+
+    #[rstest]
+    fn test_median(named_array_fixture: NamedArray<f64>) {
+        assert_eq!(named_array_fixture.median().unwrap(), 4.0);
+        let even = NamedArray {
+            name: "even".to_string(),
+            data: vec![1, 2, 3, 4],
+        };
+        assert_eq!(even.median().unwrap(), 2.5);
+    }
+
+    #[rstest]
+    #[case(QuantileMethod::Linear, 2.8)]
+    #[case(QuantileMethod::Lower, 2.0)]
+    #[case(QuantileMethod::Higher, 3.0)]
+    #[case(QuantileMethod::Nearest, 3.0)]
+    #[case(QuantileMethod::Midpoint, 2.5)]
+    fn test_quantile_methods(#[case] method: QuantileMethod, #[case] expected: f64) {
+        let named_array = NamedArray {
+            name: "x".to_string(),
+            data: vec![4.0, 1.0, 3.0, 2.0],
+        };
+        assert_close(named_array.quantile(0.6, method).unwrap(), expected);
+    }
+
+    #[rstest]
+    fn test_quantiles(named_array_fixture: NamedArray<f64>) {
+        let res = named_array_fixture
+            .quantiles(&[0.0, 0.25, 1.0], QuantileMethod::Linear)
+            .unwrap();
+        assert_eq!(res, vec![1.0, 2.0, 10.0]);
+        assert!(named_array_fixture
+            .quantile(1.5, QuantileMethod::Linear)
+            .is_err());
+    }
+
+    #[rstest]
+    fn test_skewness_kurtosis(named_array_fixture: NamedArray<f64>) {
+        assert_close(named_array_fixture.skewness().unwrap(), 0.28867513459481287);
+        assert_close(named_array_fixture.kurtosis().unwrap(), -1.55);
+        let constant = NamedArray {
+            name: "c".to_string(),
+            data: vec![1.0, 1.0, 1.0],
+        };
+        assert!(constant.skewness().is_err());
+    }
+
+    #[rstest]
+    fn test_cov(named_array_fixture: NamedArray<f64>) {
+        assert_close(
+            named_array_fixture.cov(&named_array_fixture, 1).unwrap(),
+            15.0,
+        );
+    }
+
+    #[rstest]
+    fn test_correlation() {
+        let x = NamedArray {
+            name: "x".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+        };
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: vec![1.0, 4.0, 9.0, 16.0, 100.0],
+        };
+        assert_close(x.pearson(&y).unwrap(), 0.7952035738296035);
+        assert_close(x.spearman(&y).unwrap(), 1.0);
+    }
+
+    #[rstest]
+    fn test_spearman_ties() {
+        let x = NamedArray {
+            name: "x".to_string(),
+            data: vec![1.0, 2.0, 2.0, 3.0],
+        };
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: vec![1.0, 3.0, 2.0, 4.0],
+        };
+        assert_close(x.spearman(&y).unwrap(), 0.9486832980505138);
+    }
+
+    #[rstest]
+    fn test_correlation_mismatch(named_array_fixture: NamedArray<f64>) {
+        let short = NamedArray {
+            name: "short".to_string(),
+            data: vec![1.0, 2.0],
+        };
+        assert!(named_array_fixture.pearson(&short).is_err());
+        assert!(named_array_fixture.cov(&short, 0).is_err());
+    }
+}
@@ -39,6 +39,7 @@ impl Display for ErrString {
 pub enum RustLearnError {
     ValidationError(ErrString),
     NotYetImplementedError(ErrString),
+    IoError(ErrString),
 }
 
 impl Error for RustLearnError {}
@@ -49,6 +50,7 @@ impl Display for RustLearnError {
         match self {
             ValidationError(msg) => write!(f, "assertion failed: {msg}"),
             NotYetImplementedError(msg) => write!(f, "not yet implemented: {msg}"),
+            IoError(msg) => write!(f, "io error: {msg}"),
         }
     }
 }
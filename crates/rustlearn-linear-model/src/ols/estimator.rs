@@ -0,0 +1,116 @@
+use crate::ols::linear_regression::LinearRegression;
+use crate::types::LinearRegressionReturn;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+use rustlearn_model_selection::estimator::Estimator;
+
+// ordinary least squares has no hyperparameters, so this keeps the default
+// set_param and cannot be tuned by the searches in rustlearn-model-selection;
+// it can still be cross validated and scored
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LinearRegressionEstimator {
+    pub coefficients: Option<LinearRegressionReturn>,
+}
+
+pub type Result<LinearRegressionEstimator> =
+    std::result::Result<LinearRegressionEstimator, RustLearnError>;
+
+impl LinearRegressionEstimator {
+    pub fn new() -> Self {
+        Self { coefficients: None }
+    }
+}
+
+impl Estimator for LinearRegressionEstimator {
+    fn fit(&mut self, x: &[NamedArray<f64>], y: &NamedArray<f64>) -> Result<()> {
+        let lin_reg = LinearRegression::new(x.to_vec(), y.clone())?;
+        self.coefficients = Some(lin_reg.fit()?);
+        Ok(())
+    }
+
+    fn predict(&self, x: &[NamedArray<f64>]) -> Result<NamedArray<f64>> {
+        match &self.coefficients {
+            Some(coefficients) => coefficients.predict(x),
+            None => Err(ValidationError(ErrString::from(
+                "estimator has not been fit",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+    use rustlearn_model_selection::cross_validate::cross_validate;
+    use rustlearn_model_selection::cross_validation::kfold::KFold;
+    use rustlearn_model_selection::scoring::Scorer;
+
+    #[fixture]
+    fn input_named_array_multi() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "age".to_string(),
+                data: vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            },
+            NamedArray {
+                name: "bmi".to_string(),
+                data: vec![2.0, 1.0, 4.0, 3.0, 6.0, 5.0],
+            },
+        ]
+    }
+
+    // an exact linear relationship, so every fold recovers it
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![5.0, 4.0, 11.0, 10.0, 17.0, 16.0],
+        }
+    }
+
+    #[rstest]
+    fn test_fit_predict(
+        input_named_array_multi: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let mut estimator = LinearRegressionEstimator::new();
+        assert!(estimator.predict(&input_named_array_multi).is_err());
+
+        estimator
+            .fit(&input_named_array_multi, &target_named_array)
+            .unwrap();
+        let pred = estimator.predict(&input_named_array_multi).unwrap();
+        for (val, expected) in pred.data.iter().zip(target_named_array.data.iter()) {
+            assert_float_relative_eq!(*val, *expected, 0.001);
+        }
+
+        let missing = vec![input_named_array_multi[0].clone()];
+        assert!(estimator.predict(&missing).is_err());
+    }
+
+    #[rstest]
+    fn test_cross_validate(
+        input_named_array_multi: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let res = cross_validate(
+            &LinearRegressionEstimator::new(),
+            &input_named_array_multi,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            &[Scorer::MeanSquaredError],
+            true,
+        )
+        .unwrap();
+        for v in res.test_scores.get("mean_squared_error").unwrap() {
+            assert!(*v < 1e-6);
+        }
+        let pred = res.predictions.unwrap();
+        for (val, expected) in pred.data.iter().zip(target_named_array.data.iter()) {
+            assert_float_relative_eq!(*val, *expected, 0.001);
+        }
+    }
+}
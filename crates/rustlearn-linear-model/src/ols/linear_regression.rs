@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 
 use crate::types::LinearRegressionReturn;
-use ndarray::prelude::*;
-use ndarray::{Array, ArrayView};
+use ndarray::ArrayView;
 use ndarray_linalg::solve::Inverse;
 use num::{Num, ToPrimitive};
 use rustlearn_array::namedarray::NamedArray;
+use rustlearn_array::ndarray::to_array2;
 use rustlearn_errors::RustLearnError::ValidationError;
 use rustlearn_errors::{ErrString, RustLearnError};
 use std::iter;
@@ -90,14 +90,12 @@ where
         T: Into<f64>,
     {
         let mut x_copy: Vec<NamedArray<f64>> = Vec::new();
-        let mut col_names: Vec<String> = Vec::new();
         for nm in self.x.clone().iter() {
             let updated = nm.data.iter().map(|x| x.to_f64().unwrap()).collect();
             x_copy.push(NamedArray {
                 name: nm.name.clone(),
                 data: updated,
             });
-            col_names.push(nm.name.clone());
         }
         let zeros: Vec<f64> = iter::repeat_n(1.0, x_copy[0].len()).collect();
         let intercepts = NamedArray {
@@ -106,11 +104,7 @@ where
         };
         x_copy.insert(0, intercepts);
 
-        let mut x_matrix = Array::<f64, _>::zeros((x_copy[0].len(), 0).f());
-
-        for col in x_copy.iter() {
-            x_matrix.push_column(ArrayView::from(&col.data)).unwrap();
-        }
+        let (x_matrix, col_names) = to_array2(&x_copy)?;
 
         let x_transpose = x_matrix.t();
         let x_transpose_x = x_transpose.dot(&x_matrix);
@@ -133,7 +127,7 @@ where
                 if i == 0 {
                     intercepts = *v;
                 } else {
-                    coefs.insert(col_names[i - 1].clone(), *v);
+                    coefs.insert(col_names[i].clone(), *v);
                 }
             }
         }
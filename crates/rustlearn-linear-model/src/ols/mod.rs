@@ -1 +1,3 @@
+pub mod estimator;
 pub mod linear_regression;
+pub mod sparse_linear_regression;
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use crate::types::LinearRegressionReturn;
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_array::sparse::{CscMatrix, CsrMatrix};
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SparseLinearRegression<T> {
+    pub x: CscMatrix<T>,
+    pub y: NamedArray<T>,
+    pub tolerance: f64,
+    pub max_iter: usize,
+}
+
+pub type Result<SparseLinearRegression> =
+    std::result::Result<SparseLinearRegression, RustLearnError>;
+
+fn norm_squared(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum()
+}
+
+impl<T> SparseLinearRegression<T>
+where
+    T: Num + Copy + ToPrimitive,
+{
+    pub fn new(x: CscMatrix<T>, y: NamedArray<T>) -> Result<Self> {
+        if y.data.is_empty() {
+            return Err(ValidationError(ErrString::from(
+                "target is an empty vector",
+            )));
+        }
+        if x.n_cols() == 0 {
+            return Err(ValidationError(ErrString::from("no x-values provided")));
+        }
+        if x.n_rows != y.len() {
+            return Err(ValidationError(ErrString::from("mismatch x and y lengths")));
+        }
+        Ok(Self {
+            x,
+            y,
+            tolerance: 1e-10,
+            max_iter: 1000,
+        })
+    }
+
+    // the design matrix with a leading column of ones, applied without
+    // materializing that column
+    fn design_mul_vec(&self, v: &[f64]) -> Result<Vec<f64>> {
+        let mut out = self.x.mul_vec(&v[1..])?;
+        for o in out.iter_mut() {
+            *o += v[0];
+        }
+        Ok(out)
+    }
+
+    fn design_transpose_mul_vec(&self, v: &[f64]) -> Result<Vec<f64>> {
+        let mut out = vec![v.iter().sum()];
+        out.extend(self.x.transpose_mul_vec(v)?);
+        Ok(out)
+    }
+
+    // conjugate gradients on the normal equations (CGLS), so only products
+    // with the sparse matrix and its transpose are ever needed
+    pub fn fit(self) -> Result<LinearRegressionReturn> {
+        let n_coefs = self.x.n_cols() + 1;
+        let mut coefs = vec![0.0; n_coefs];
+        let mut residual: Vec<f64> = self.y.data.iter().map(|x| x.to_f64().unwrap()).collect();
+        let mut gradient = self.design_transpose_mul_vec(&residual)?;
+        let mut direction = gradient.clone();
+        let mut gamma = norm_squared(&gradient);
+        let threshold = self.tolerance * gamma.sqrt();
+
+        let mut converged = gamma.sqrt() <= threshold;
+        for _ in 0..self.max_iter {
+            if converged {
+                break;
+            }
+            let q = self.design_mul_vec(&direction)?;
+            let q_norm = norm_squared(&q);
+            if q_norm == 0.0 {
+                break;
+            }
+            let alpha = gamma / q_norm;
+            for (c, d) in coefs.iter_mut().zip(direction.iter()) {
+                *c += alpha * d;
+            }
+            for (r, q_i) in residual.iter_mut().zip(q.iter()) {
+                *r -= alpha * q_i;
+            }
+            gradient = self.design_transpose_mul_vec(&residual)?;
+            let gamma_new = norm_squared(&gradient);
+            converged = gamma_new.sqrt() <= threshold;
+
+            let beta = gamma_new / gamma;
+            for (d, g) in direction.iter_mut().zip(gradient.iter()) {
+                *d = g + beta * *d;
+            }
+            gamma = gamma_new;
+        }
+        if !converged {
+            return Err(ValidationError(ErrString::from(
+                "solver did not converge within max_iter iterations",
+            )));
+        }
+
+        let mut beta_values: HashMap<String, f64> = HashMap::new();
+        for (name, coef) in self.x.names.iter().zip(coefs[1..].iter()) {
+            beta_values.insert(name.clone(), *coef);
+        }
+        Ok(LinearRegressionReturn {
+            intercept: coefs[0],
+            beta_values,
+        })
+    }
+
+    pub fn predict(
+        self,
+        new_x: CsrMatrix<T>,
+        return_object: LinearRegressionReturn,
+    ) -> Result<NamedArray<f64>> {
+        for name in return_object.beta_values.keys() {
+            if !new_x.names.contains(name) {
+                return Err(ValidationError(ErrString::from(format!(
+                    "no column named {name}"
+                ))));
+            }
+        }
+        let mut coefs: Vec<f64> = Vec::new();
+        for name in new_x.names.iter() {
+            match return_object.beta_values.get(name) {
+                Some(coef) => coefs.push(*coef),
+                None => {
+                    return Err(ValidationError(ErrString::from(format!(
+                        "no coefficient for column {name}"
+                    ))))
+                }
+            }
+        }
+        let sums = new_x.mul_vec(&coefs)?;
+
+        Ok(NamedArray {
+            name: "predictions".to_string(),
+            data: sums.iter().map(|x| x + return_object.intercept).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ols::linear_regression::LinearRegression;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array_multi() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "age".to_string(),
+                data: vec![0.038076, -0.001882, 0.085299, -0.089063, 0.005383],
+            },
+            NamedArray {
+                name: "bmi".to_string(),
+                data: vec![0.061696, -0.051474, 0.044451, -0.011595, -0.036385],
+            },
+        ]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![151.0, 75.0, 141.0, 206.0, 135.0],
+        }
+    }
+
+    #[fixture]
+    fn one_hot() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "red".to_string(),
+                data: vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            },
+            NamedArray {
+                name: "green".to_string(),
+                data: vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            },
+        ]
+    }
+
+    #[rstest]
+    fn test_instantiation_errors(target_named_array: NamedArray<f64>) {
+        let x = CscMatrix::from_dense(&[NamedArray {
+            name: "x".to_string(),
+            data: vec![1.0, 0.0],
+        }])
+        .unwrap();
+        assert!(SparseLinearRegression::new(x.clone(), target_named_array).is_err());
+
+        let empty = NamedArray {
+            name: "y".to_string(),
+            data: Vec::new(),
+        };
+        assert!(SparseLinearRegression::new(x, empty).is_err());
+    }
+
+    #[rstest]
+    fn test_fit_matches_dense(
+        input_named_array_multi: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let dense =
+            LinearRegression::new(input_named_array_multi.clone(), target_named_array.clone())
+                .unwrap()
+                .fit()
+                .unwrap();
+        let x = CscMatrix::from_dense(&input_named_array_multi).unwrap();
+        let sparse = SparseLinearRegression::new(x, target_named_array)
+            .unwrap()
+            .fit()
+            .unwrap();
+
+        assert_float_relative_eq!(sparse.intercept, dense.intercept, 0.001);
+        for (coef, val) in dense.beta_values.iter() {
+            let res_v = sparse.beta_values.get(coef).unwrap().to_owned();
+            assert_float_relative_eq!(res_v, val.to_owned(), 0.001)
+        }
+    }
+
+    #[rstest]
+    fn test_fit_one_hot(one_hot: Vec<NamedArray<f64>>) {
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: vec![3.0, 5.0, 1.0, 3.0, 5.0, 1.0],
+        };
+        let x = CscMatrix::from_dense(&one_hot).unwrap();
+        let res = SparseLinearRegression::new(x, y).unwrap().fit().unwrap();
+
+        assert_float_relative_eq!(res.intercept, 1.0, 0.001);
+        assert_float_relative_eq!(*res.beta_values.get("red").unwrap(), 2.0, 0.001);
+        assert_float_relative_eq!(*res.beta_values.get("green").unwrap(), 4.0, 0.001);
+    }
+
+    #[rstest]
+    fn test_predict(one_hot: Vec<NamedArray<f64>>) {
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: vec![3.0, 5.0, 1.0, 3.0, 5.0, 1.0],
+        };
+        let x = CscMatrix::from_dense(&one_hot).unwrap();
+        let lin_reg = SparseLinearRegression::new(x.clone(), y).unwrap();
+        let res = lin_reg.clone().fit().unwrap();
+        let pred = lin_reg.predict(x.to_csr(), res).unwrap();
+
+        for (val, expected) in pred.data.iter().zip([3.0, 5.0, 1.0, 3.0, 5.0, 1.0]) {
+            assert_float_relative_eq!(val.to_owned(), expected, 0.001)
+        }
+    }
+
+    #[rstest]
+    fn test_predict_unknown_column(one_hot: Vec<NamedArray<f64>>) {
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: vec![3.0, 5.0, 1.0, 3.0, 5.0, 1.0],
+        };
+        let x = CscMatrix::from_dense(&one_hot).unwrap();
+        let lin_reg = SparseLinearRegression::new(x, y).unwrap();
+        let res = lin_reg.clone().fit().unwrap();
+        let other = CscMatrix::from_dense(&[NamedArray {
+            name: "blue".to_string(),
+            data: vec![1.0, 0.0],
+        }])
+        .unwrap();
+        assert!(lin_reg.predict(other.to_csr(), res).is_err());
+    }
+
+    #[rstest]
+    fn test_predict_missing_column(one_hot: Vec<NamedArray<f64>>) {
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: vec![3.0, 5.0, 1.0, 3.0, 5.0, 1.0],
+        };
+        let x = CscMatrix::from_dense(&one_hot).unwrap();
+        let lin_reg = SparseLinearRegression::new(x, y).unwrap();
+        let res = lin_reg.clone().fit().unwrap();
+        let red_only = CscMatrix::from_dense(&one_hot[..1]).unwrap();
+        assert!(lin_reg.predict(red_only.to_csr(), res).is_err());
+    }
+}
@@ -1,7 +1,74 @@
 use std::collections::HashMap;
 
-#[derive(Debug, PartialEq)]
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct LinearRegressionReturn {
     pub intercept: f64,
     pub beta_values: HashMap<String, f64>,
 }
+
+impl LinearRegressionReturn {
+    // matches columns to coefficients by name, so every fitted column must
+    // be present; extra columns are ignored
+    pub fn predict(&self, new_x: &[NamedArray<f64>]) -> Result<NamedArray<f64>, RustLearnError> {
+        if new_x.is_empty() {
+            return Err(ValidationError(ErrString::from("no x-values provided")));
+        }
+        let n_rows = new_x[0].data.len();
+        let mut data = vec![self.intercept; n_rows];
+        for (name, beta) in self.beta_values.iter() {
+            let column = match new_x.iter().find(|nm| nm.name == *name) {
+                Some(column) => column,
+                None => {
+                    return Err(ValidationError(ErrString::from(format!(
+                        "no column named {name}"
+                    ))))
+                }
+            };
+            if column.data.len() != n_rows {
+                return Err(ValidationError(ErrString::from("mismatch x lengths")));
+            }
+            for (prediction, x) in data.iter_mut().zip(column.data.iter()) {
+                *prediction += beta * x;
+            }
+        }
+        Ok(NamedArray {
+            name: "predictions".to_string(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn coefficients() -> LinearRegressionReturn {
+        LinearRegressionReturn {
+            intercept: 1.0,
+            beta_values: HashMap::from([("age".to_string(), 2.0), ("bmi".to_string(), -1.0)]),
+        }
+    }
+
+    #[rstest]
+    fn test_predict(coefficients: LinearRegressionReturn) {
+        let x = vec![
+            NamedArray {
+                name: "bmi".to_string(),
+                data: vec![1.0, 0.0],
+            },
+            NamedArray {
+                name: "age".to_string(),
+                data: vec![1.0, 3.0],
+            },
+        ];
+        assert_eq!(coefficients.predict(&x).unwrap().data, vec![2.0, 7.0]);
+        assert!(coefficients.predict(&x[..1]).is_err());
+        assert!(coefficients.predict(&[]).is_err());
+    }
+}
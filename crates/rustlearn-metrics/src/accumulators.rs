@@ -0,0 +1,493 @@
+use crate::confusion_matrix::{ConfusionMatrix, Normalize};
+use crate::core::base::length_checks;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+fn empty_error() -> RustLearnError {
+    RustLearnError::ValidationError(ErrString::from("no values have been accumulated"))
+}
+
+fn pairs_f64<'a, T>(
+    y_true: &'a NamedArray<T>,
+    y_pred: &'a NamedArray<T>,
+) -> impl Iterator<Item = (f64, f64)> + 'a
+where
+    T: ToPrimitive,
+{
+    y_true
+        .data
+        .iter()
+        .zip(y_pred.data.iter())
+        .map(|(t, p)| (t.to_f64().unwrap(), p.to_f64().unwrap()))
+}
+
+// mean updated incrementally rather than from a running sum, so it does not
+// lose precision as the count grows
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RunningMean {
+    pub count: usize,
+    pub mean: f64,
+}
+
+impl RunningMean {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.mean += (value - self.mean) / self.count as f64;
+    }
+
+    pub fn merge(&mut self, other: &RunningMean) {
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        self.mean += (other.mean - self.mean) * other.count as f64 / count as f64;
+        self.count = count;
+    }
+}
+
+// Welford's mean and sum of squared deviations, merged with Chan's update
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Welford {
+    pub count: usize,
+    pub mean: f64,
+    pub m2: f64,
+}
+
+impl Welford {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    pub fn merge(&mut self, other: &Welford) {
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * (self.count * other.count) as f64 / count as f64;
+        self.count = count;
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct MseAccumulator {
+    pub squared_errors: RunningMean,
+}
+
+impl MseAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update<T>(
+        &mut self,
+        y_true: &NamedArray<T>,
+        y_pred: &NamedArray<T>,
+    ) -> Result<(), RustLearnError>
+    where
+        T: ToPrimitive,
+    {
+        length_checks(y_true, y_pred)?;
+        for (t, p) in pairs_f64(y_true, y_pred) {
+            self.squared_errors.push((t - p).powi(2));
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &MseAccumulator) {
+        self.squared_errors.merge(&other.squared_errors);
+    }
+
+    pub fn finalize(&self) -> Result<f64, RustLearnError> {
+        if self.squared_errors.count == 0 {
+            return Err(empty_error());
+        }
+        Ok(self.squared_errors.mean)
+    }
+
+    pub fn finalize_root(&self) -> Result<f64, RustLearnError> {
+        Ok(self.finalize()?.sqrt())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct MaeAccumulator {
+    pub absolute_errors: RunningMean,
+}
+
+impl MaeAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update<T>(
+        &mut self,
+        y_true: &NamedArray<T>,
+        y_pred: &NamedArray<T>,
+    ) -> Result<(), RustLearnError>
+    where
+        T: ToPrimitive,
+    {
+        length_checks(y_true, y_pred)?;
+        for (t, p) in pairs_f64(y_true, y_pred) {
+            self.absolute_errors.push((t - p).abs());
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &MaeAccumulator) {
+        self.absolute_errors.merge(&other.absolute_errors);
+    }
+
+    pub fn finalize(&self) -> Result<f64, RustLearnError> {
+        if self.absolute_errors.count == 0 {
+            return Err(empty_error());
+        }
+        Ok(self.absolute_errors.mean)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct R2Accumulator {
+    pub y_true: Welford,
+    pub squared_errors: RunningMean,
+}
+
+impl R2Accumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update<T>(
+        &mut self,
+        y_true: &NamedArray<T>,
+        y_pred: &NamedArray<T>,
+    ) -> Result<(), RustLearnError>
+    where
+        T: ToPrimitive,
+    {
+        length_checks(y_true, y_pred)?;
+        for (t, p) in pairs_f64(y_true, y_pred) {
+            self.y_true.push(t);
+            self.squared_errors.push((t - p).powi(2));
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &R2Accumulator) {
+        self.y_true.merge(&other.y_true);
+        self.squared_errors.merge(&other.squared_errors);
+    }
+
+    pub fn finalize(&self) -> Result<f64, RustLearnError> {
+        if self.y_true.count == 0 {
+            return Err(empty_error());
+        }
+        if self.y_true.m2 == 0.0 {
+            return Err(RustLearnError::ValidationError(ErrString::from(
+                "r2 is undefined for a constant target",
+            )));
+        }
+        let ss_res = self.squared_errors.mean * self.squared_errors.count as f64;
+        Ok(1.0 - ss_res / self.y_true.m2)
+    }
+}
+
+// labels are kept sorted and the count matrix grows as new labels arrive
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ConfusionMatrixAccumulator {
+    pub labels: Vec<f64>,
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl ConfusionMatrixAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn label_index(&mut self, label: f64) -> usize {
+        match self.labels.binary_search_by(|l| l.total_cmp(&label)) {
+            Ok(idx) => idx,
+            Err(idx) => {
+                self.labels.insert(idx, label);
+                for row in self.counts.iter_mut() {
+                    row.insert(idx, 0);
+                }
+                self.counts.insert(idx, vec![0; self.labels.len()]);
+                idx
+            }
+        }
+    }
+
+    fn add(&mut self, true_label: f64, pred_label: f64, count: usize) {
+        // inserting the predicted label can shift the true label's index, so
+        // both are registered before either is looked up
+        self.label_index(true_label);
+        let col = self.label_index(pred_label);
+        let row = self.label_index(true_label);
+        self.counts[row][col] += count;
+    }
+
+    pub fn update<T>(
+        &mut self,
+        y_true: &NamedArray<T>,
+        y_pred: &NamedArray<T>,
+    ) -> Result<(), RustLearnError>
+    where
+        T: ToPrimitive,
+    {
+        length_checks(y_true, y_pred)?;
+        for (t, p) in pairs_f64(y_true, y_pred) {
+            self.add(t, p, 1);
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &ConfusionMatrixAccumulator) {
+        for (i, true_label) in other.labels.iter().enumerate() {
+            for (j, pred_label) in other.labels.iter().enumerate() {
+                if other.counts[i][j] > 0 {
+                    self.add(*true_label, *pred_label, other.counts[i][j]);
+                }
+            }
+        }
+    }
+
+    pub fn finalize(
+        &self,
+        normalize: Option<Normalize>,
+    ) -> Result<ConfusionMatrix, RustLearnError> {
+        if self.labels.is_empty() {
+            return Err(empty_error());
+        }
+        let matrix = self
+            .counts
+            .iter()
+            .map(|row| row.iter().map(|c| *c as f64).collect())
+            .collect();
+        Ok(ConfusionMatrix::from_counts(
+            self.labels.clone(),
+            matrix,
+            normalize,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confusion_matrix::confusion_matrix;
+    use crate::mean_absolute_error::mean_absolute_error;
+    use crate::mean_squared_error::mean_squared_error;
+    use crate::r_2::r_2;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![151.0, 75.0, 141.0, 206.0, 135.0, 97.0, 168.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![140.0, 86.0, 120.0, 240.0, 140.0, 101.0, 150.0],
+        }
+    }
+
+    fn batches(named_array: &NamedArray<f64>, sizes: &[usize]) -> Vec<NamedArray<f64>> {
+        let mut start = 0;
+        sizes
+            .iter()
+            .map(|size| {
+                let batch = NamedArray {
+                    name: named_array.name.clone(),
+                    data: named_array.data[start..start + size].to_vec(),
+                };
+                start += size;
+                batch
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn test_batches_match_full(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let mut mse = MseAccumulator::new();
+        let mut mae = MaeAccumulator::new();
+        let mut r2 = R2Accumulator::new();
+        let y_true = batches(&target_named_array, &[3, 1, 3]);
+        let y_pred = batches(&predictions_named_array, &[3, 1, 3]);
+        for (t, p) in y_true.iter().zip(y_pred.iter()) {
+            mse.update(t, p).unwrap();
+            mae.update(t, p).unwrap();
+            r2.update(t, p).unwrap();
+        }
+
+        let (t, p) = (target_named_array, predictions_named_array);
+        assert_float_relative_eq!(
+            mse.finalize().unwrap(),
+            mean_squared_error(t.clone(), p.clone(), None).unwrap(),
+            0.001
+        );
+        assert_float_relative_eq!(
+            mse.finalize_root().unwrap(),
+            mean_squared_error(t.clone(), p.clone(), None)
+                .unwrap()
+                .sqrt(),
+            0.001
+        );
+        assert_float_relative_eq!(
+            mae.finalize().unwrap(),
+            mean_absolute_error(t.clone(), p.clone(), None).unwrap(),
+            0.001
+        );
+        assert_float_relative_eq!(r2.finalize().unwrap(), r_2(t, p, None).unwrap(), 0.001);
+    }
+
+    #[rstest]
+    fn test_merge_matches_single(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let mut single = R2Accumulator::new();
+        single
+            .update(&target_named_array, &predictions_named_array)
+            .unwrap();
+
+        let y_true = batches(&target_named_array, &[2, 5]);
+        let y_pred = batches(&predictions_named_array, &[2, 5]);
+        let mut left = R2Accumulator::new();
+        let mut right = R2Accumulator::new();
+        left.update(&y_true[0], &y_pred[0]).unwrap();
+        right.update(&y_true[1], &y_pred[1]).unwrap();
+        left.merge(&right);
+        left.merge(&R2Accumulator::new());
+
+        assert_eq!(left.y_true.count, 7);
+        assert_float_relative_eq!(left.y_true.mean, single.y_true.mean, 0.001);
+        assert_float_relative_eq!(left.y_true.m2, single.y_true.m2, 0.001);
+        assert_float_relative_eq!(left.finalize().unwrap(), single.finalize().unwrap(), 0.001);
+
+        let mut empty = MseAccumulator::new();
+        let mut full = MseAccumulator::new();
+        full.update(&target_named_array, &predictions_named_array)
+            .unwrap();
+        empty.merge(&full);
+        assert_eq!(empty, full);
+    }
+
+    #[rstest]
+    fn test_large_offset() {
+        // a naive sum of squares loses every digit of the variance here
+        let offset = 1e9;
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![offset + 1.0, offset + 2.0, offset + 3.0, offset + 4.0],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![offset + 1.0, offset + 2.0, offset + 3.0, offset + 5.0],
+        };
+        let mut r2 = R2Accumulator::new();
+        r2.update(&y_true, &y_pred).unwrap();
+        assert_float_relative_eq!(r2.finalize().unwrap(), 0.8, 0.001);
+    }
+
+    #[rstest]
+    fn test_errors(target_named_array: NamedArray<f64>) {
+        assert!(MseAccumulator::new().finalize().is_err());
+        assert!(MaeAccumulator::new().finalize().is_err());
+        assert!(R2Accumulator::new().finalize().is_err());
+        assert!(ConfusionMatrixAccumulator::new().finalize(None).is_err());
+
+        let short = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0],
+        };
+        assert!(MseAccumulator::new()
+            .update(&target_named_array, &short)
+            .is_err());
+
+        let mut constant = R2Accumulator::new();
+        constant.update(&short, &short).unwrap();
+        assert!(constant.finalize().is_err());
+    }
+
+    #[rstest]
+    fn test_confusion_matrix_accumulator() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 2, 0, 1, 2],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1, 0, 0, 1],
+        };
+        let expected = confusion_matrix(y_true.clone(), y_pred.clone(), None).unwrap();
+
+        // label 2 first appears in the second batch of the left worker
+        let mut left = ConfusionMatrixAccumulator::new();
+        left.update(
+            &NamedArray {
+                name: "target".to_string(),
+                data: vec![1, 0],
+            },
+            &NamedArray {
+                name: "predictions".to_string(),
+                data: vec![0, 0],
+            },
+        )
+        .unwrap();
+        left.update(
+            &NamedArray {
+                name: "target".to_string(),
+                data: vec![1],
+            },
+            &NamedArray {
+                name: "predictions".to_string(),
+                data: vec![2],
+            },
+        )
+        .unwrap();
+        let mut right = ConfusionMatrixAccumulator::new();
+        right
+            .update(
+                &NamedArray {
+                    name: "target".to_string(),
+                    data: vec![0, 2, 2],
+                },
+                &NamedArray {
+                    name: "predictions".to_string(),
+                    data: vec![0, 1, 1],
+                },
+            )
+            .unwrap();
+        left.merge(&right);
+
+        assert_eq!(left.finalize(None).unwrap(), expected);
+        assert_eq!(
+            left.finalize(Some(Normalize::True)).unwrap(),
+            confusion_matrix(y_true, y_pred, Some(Normalize::True)).unwrap()
+        );
+    }
+}
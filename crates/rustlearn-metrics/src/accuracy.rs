@@ -0,0 +1,145 @@
+use crate::core::classification::{class_counts, classification_checks};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+pub fn accuracy_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let correct = y_true
+        .iter()
+        .zip(y_pred.iter())
+        .filter(|(t, p)| t == p)
+        .count();
+    Ok(correct as f64 / y_true.len() as f64)
+}
+
+// mean recall over the classes present in y_true
+pub fn balanced_accuracy_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let recalls: Vec<f64> = class_counts(&y_true, &y_pred)
+        .iter()
+        .filter(|c| c.support > 0)
+        .map(|c| c.true_positives as f64 / c.support as f64)
+        .collect();
+    Ok(recalls.iter().sum::<f64>() / recalls.len() as f64)
+}
+
+pub fn hamming_loss<T>(y_true: NamedArray<T>, y_pred: NamedArray<T>) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    Ok(1.0 - accuracy_score(y_true, y_pred)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 2, 0, 1, 2],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1, 0, 0, 1],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array_missing() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1],
+        }
+    }
+
+    #[rstest]
+    fn test_accuracy(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array: NamedArray<i32>,
+    ) {
+        assert_float_relative_eq!(
+            accuracy_score(target_named_array.clone(), predictions_named_array.clone()).unwrap(),
+            1.0 / 3.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            hamming_loss(target_named_array, predictions_named_array).unwrap(),
+            2.0 / 3.0,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_balanced_accuracy(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array: NamedArray<i32>,
+    ) {
+        assert_float_relative_eq!(
+            balanced_accuracy_score(target_named_array, predictions_named_array).unwrap(),
+            1.0 / 3.0,
+            0.001
+        );
+
+        // a classifier that always says 0 on imbalanced data
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 0, 0, 1],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 0, 0, 0],
+        };
+        assert_eq!(
+            accuracy_score(y_true.clone(), y_pred.clone()).unwrap(),
+            0.75
+        );
+        assert_eq!(balanced_accuracy_score(y_true, y_pred).unwrap(), 0.5);
+    }
+
+    #[rstest]
+    fn test_mismatch_error(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array_missing: NamedArray<i32>,
+    ) {
+        assert!(accuracy_score(
+            target_named_array.clone(),
+            predictions_named_array_missing.clone()
+        )
+        .is_err());
+        assert!(
+            balanced_accuracy_score(target_named_array, predictions_named_array_missing).is_err()
+        );
+        let empty: NamedArray<i32> = NamedArray {
+            name: "empty".to_string(),
+            data: vec![],
+        };
+        assert!(accuracy_score(empty.clone(), empty).is_err());
+    }
+}
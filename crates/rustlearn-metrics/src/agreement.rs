@@ -0,0 +1,135 @@
+use crate::core::classification::{class_counts, classification_checks};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+// correct predictions, total rows and the sums of products and squares of the
+// per-class true and predicted counts, which both scores are built from
+fn agreement_counts(y_true: &[f64], y_pred: &[f64]) -> (f64, f64, f64, f64, f64) {
+    let counts = class_counts(y_true, y_pred);
+    let correct: usize = counts.iter().map(|c| c.true_positives).sum();
+    let mut products = 0.0;
+    let mut true_squares = 0.0;
+    let mut pred_squares = 0.0;
+    for c in counts.iter() {
+        let t = c.support as f64;
+        let p = (c.true_positives + c.false_positives) as f64;
+        products += t * p;
+        true_squares += t * t;
+        pred_squares += p * p;
+    }
+    (
+        correct as f64,
+        y_true.len() as f64,
+        products,
+        true_squares,
+        pred_squares,
+    )
+}
+
+pub fn matthews_corrcoef<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let (correct, n, products, true_squares, pred_squares) = agreement_counts(&y_true, &y_pred);
+    let denominator = ((n * n - pred_squares) * (n * n - true_squares)).sqrt();
+    if denominator == 0.0 {
+        return Ok(0.0);
+    }
+    Ok((correct * n - products) / denominator)
+}
+
+pub fn cohen_kappa_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let (correct, n, products, _, _) = agreement_counts(&y_true, &y_pred);
+    let observed = correct / n;
+    let expected = products / (n * n);
+    if expected == 1.0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "kappa is undefined when both series hold a single label",
+        )));
+    }
+    Ok((observed - expected) / (1.0 - expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn binary_target() -> NamedArray<i32> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 1, 0, 1, 1],
+        }
+    }
+
+    #[fixture]
+    fn binary_predictions() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 1, 0, 0, 1, 1],
+        }
+    }
+
+    #[rstest]
+    fn test_matthews(binary_target: NamedArray<i32>, binary_predictions: NamedArray<i32>) {
+        assert_float_relative_eq!(
+            matthews_corrcoef(binary_target.clone(), binary_predictions).unwrap(),
+            0.5_f64.sqrt(),
+            0.001
+        );
+        assert_eq!(
+            matthews_corrcoef(binary_target.clone(), binary_target).unwrap(),
+            1.0
+        );
+    }
+
+    #[rstest]
+    fn test_matthews_multiclass() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 2, 0, 1, 2],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1, 0, 0, 1],
+        };
+        // 2 correct of 6, predicted counts (3, 2, 1) against true (2, 2, 2)
+        assert_float_relative_eq!(
+            matthews_corrcoef(y_true, y_pred).unwrap(),
+            (2.0 * 6.0 - 12.0) / ((36.0_f64 - 14.0) * (36.0 - 12.0)).sqrt(),
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_kappa(binary_target: NamedArray<i32>, binary_predictions: NamedArray<i32>) {
+        assert_float_relative_eq!(
+            cohen_kappa_score(binary_target, binary_predictions).unwrap(),
+            2.0 / 3.0,
+            0.001
+        );
+        let constant = NamedArray {
+            name: "target".to_string(),
+            data: vec![1, 1, 1],
+        };
+        assert!(cohen_kappa_score(constant.clone(), constant).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::core::thresholds::{binary_checks, score_checks};
+use crate::probability::probability_checks;
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_array::stats::QuantileMethod;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BinStrategy {
+    // equal width bins over [0, 1]
+    Uniform,
+    // bins holding roughly the same number of predictions
+    Quantile,
+}
+
+// one entry per non-empty bin, in increasing order of probability
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalibrationCurve {
+    pub prob_true: Vec<f64>,
+    pub prob_pred: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+fn bin_edges(
+    y_prob: &[f64],
+    n_bins: usize,
+    strategy: BinStrategy,
+) -> Result<Vec<f64>, RustLearnError> {
+    let steps: Vec<f64> = (0..=n_bins).map(|i| i as f64 / n_bins as f64).collect();
+    match strategy {
+        BinStrategy::Uniform => Ok(steps),
+        BinStrategy::Quantile => NamedArray {
+            name: "probabilities".to_string(),
+            data: y_prob.to_vec(),
+        }
+        .quantiles(&steps, QuantileMethod::Linear),
+    }
+}
+
+pub fn calibration_curve<T>(
+    y_true: NamedArray<T>,
+    y_prob: NamedArray<f64>,
+    n_bins: usize,
+    strategy: BinStrategy,
+) -> Result<CalibrationCurve, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    if n_bins == 0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "n_bins must be at least 1",
+        )));
+    }
+    let (y_true, y_prob) = score_checks(&y_true, &y_prob)?;
+    binary_checks(&y_true, &y_prob)?;
+    probability_checks(&y_prob)?;
+
+    // a probability on an inner edge belongs to the bin above it
+    let edges = bin_edges(&y_prob, n_bins, strategy)?;
+    let inner = &edges[1..n_bins];
+    let mut sums_true = vec![0.0; n_bins];
+    let mut sums_pred = vec![0.0; n_bins];
+    let mut counts = vec![0; n_bins];
+    for (y, p) in y_true.iter().zip(y_prob.iter()) {
+        let bin = inner.iter().filter(|edge| **edge <= *p).count();
+        sums_true[bin] += y;
+        sums_pred[bin] += p;
+        counts[bin] += 1;
+    }
+
+    let mut curve = CalibrationCurve {
+        prob_true: Vec::new(),
+        prob_pred: Vec::new(),
+        counts: Vec::new(),
+    };
+    for bin in (0..n_bins).filter(|bin| counts[*bin] > 0) {
+        curve.prob_true.push(sums_true[bin] / counts[bin] as f64);
+        curve.prob_pred.push(sums_pred[bin] / counts[bin] as f64);
+        curve.counts.push(counts[bin]);
+    }
+    Ok(curve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+        }
+    }
+
+    #[fixture]
+    fn probabilities_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "probabilities".to_string(),
+            data: vec![0.1, 0.2, 0.3, 0.4, 0.65, 0.7, 0.8, 0.9, 1.0],
+        }
+    }
+
+    #[rstest]
+    fn test_uniform(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        let curve = calibration_curve(
+            target_named_array,
+            probabilities_named_array,
+            3,
+            BinStrategy::Uniform,
+        )
+        .unwrap();
+        assert_eq!(curve.prob_true, vec![0.0, 0.5, 1.0]);
+        assert_eq!(curve.counts, vec![3, 2, 4]);
+        for (actual, expected) in curve.prob_pred.iter().zip([0.2, 0.525, 0.85]) {
+            assert_float_relative_eq!(*actual, expected, 0.001);
+        }
+    }
+
+    #[rstest]
+    fn test_integer_labels(probabilities_named_array: NamedArray<f64>) {
+        let target = NamedArray {
+            name: "target".to_string(),
+            data: vec![0_i64, 0, 0, 0, 1, 1, 1, 1, 1],
+        };
+        let curve =
+            calibration_curve(target, probabilities_named_array, 3, BinStrategy::Uniform).unwrap();
+        assert_eq!(curve.prob_true, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[rstest]
+    fn test_quantile(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        let curve = calibration_curve(
+            target_named_array,
+            probabilities_named_array,
+            3,
+            BinStrategy::Quantile,
+        )
+        .unwrap();
+        assert_eq!(curve.counts, vec![3, 3, 3]);
+        for (actual, expected) in curve.prob_true.iter().zip([0.0, 2.0 / 3.0, 1.0]) {
+            assert_float_relative_eq!(*actual, expected, 0.001);
+        }
+        for (actual, expected) in curve.prob_pred.iter().zip([0.2, 1.75 / 3.0, 0.9]) {
+            assert_float_relative_eq!(*actual, expected, 0.001);
+        }
+    }
+
+    #[rstest]
+    fn test_empty_bins_dropped(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        let curve = calibration_curve(
+            target_named_array,
+            probabilities_named_array,
+            20,
+            BinStrategy::Uniform,
+        )
+        .unwrap();
+        assert_eq!(curve.counts.len(), 9);
+        assert_eq!(curve.counts.iter().sum::<usize>(), 9);
+    }
+
+    #[rstest]
+    fn test_errors(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        assert!(calibration_curve(
+            target_named_array.clone(),
+            probabilities_named_array,
+            0,
+            BinStrategy::Uniform
+        )
+        .is_err());
+        let out_of_range = NamedArray {
+            name: "probabilities".to_string(),
+            data: vec![0.1, 0.2, 0.3, 0.4, 0.65, 0.7, 0.8, 0.9, 1.5],
+        };
+        assert!(
+            calibration_curve(target_named_array, out_of_range, 3, BinStrategy::Uniform).is_err()
+        );
+    }
+}
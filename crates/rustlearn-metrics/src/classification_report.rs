@@ -0,0 +1,203 @@
+use crate::core::classification::{class_counts, classification_checks, safe_divide};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReportRow {
+    pub name: String,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1_score: f64,
+    pub support: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassificationReport {
+    pub classes: Vec<ReportRow>,
+    pub accuracy: f64,
+    pub macro_avg: ReportRow,
+    pub weighted_avg: ReportRow,
+}
+
+impl Display for ClassificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .classes
+            .iter()
+            .map(|row| row.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(self.weighted_avg.name.len());
+        let write_row = |f: &mut Formatter<'_>, row: &ReportRow| {
+            writeln!(
+                f,
+                "{:>width$}  {:>9.2}  {:>9.2}  {:>9.2}  {:>9}",
+                row.name, row.precision, row.recall, row.f1_score, row.support
+            )
+        };
+
+        writeln!(
+            f,
+            "{:>width$}  {:>9}  {:>9}  {:>9}  {:>9}",
+            "", "precision", "recall", "f1-score", "support"
+        )?;
+        writeln!(f)?;
+        for row in self.classes.iter() {
+            write_row(f, row)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>width$}  {:>9}  {:>9}  {:>9.2}  {:>9}",
+            "accuracy", "", "", self.accuracy, self.macro_avg.support
+        )?;
+        write_row(f, &self.macro_avg)?;
+        write_row(f, &self.weighted_avg)
+    }
+}
+
+fn f1(precision: f64, recall: f64) -> f64 {
+    safe_divide(2.0 * precision * recall, precision + recall)
+}
+
+pub fn classification_report<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+) -> Result<ClassificationReport, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let counts = class_counts(&y_true, &y_pred);
+
+    let classes: Vec<ReportRow> = counts
+        .iter()
+        .map(|c| {
+            let tp = c.true_positives as f64;
+            let precision = safe_divide(tp, tp + c.false_positives as f64);
+            let recall = safe_divide(tp, tp + c.false_negatives as f64);
+            ReportRow {
+                name: c.label.to_string(),
+                precision,
+                recall,
+                f1_score: f1(precision, recall),
+                support: c.support,
+            }
+        })
+        .collect();
+
+    let n_rows = y_true.len();
+    let n_classes = classes.len() as f64;
+    let average = |name: &str, weight: &dyn Fn(&ReportRow) -> f64| {
+        let total: f64 = classes.iter().map(weight).sum();
+        let mean = |score: fn(&ReportRow) -> f64| {
+            safe_divide(
+                classes.iter().map(|row| score(row) * weight(row)).sum(),
+                total,
+            )
+        };
+        ReportRow {
+            name: name.to_string(),
+            precision: mean(|row| row.precision),
+            recall: mean(|row| row.recall),
+            f1_score: mean(|row| row.f1_score),
+            support: n_rows,
+        }
+    };
+    let macro_avg = average("macro avg", &|_| 1.0 / n_classes);
+    let weighted_avg = average("weighted avg", &|row| row.support as f64);
+
+    let correct: usize = counts.iter().map(|c| c.true_positives).sum();
+    Ok(ClassificationReport {
+        accuracy: correct as f64 / n_rows as f64,
+        classes,
+        macro_avg,
+        weighted_avg,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 2, 0, 1, 2],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1, 0, 0, 1],
+        }
+    }
+
+    #[rstest]
+    fn test_classification_report(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array: NamedArray<i32>,
+    ) {
+        let report = classification_report(target_named_array, predictions_named_array).unwrap();
+        assert_eq!(report.classes.len(), 3);
+        assert_eq!(
+            report.classes[0],
+            ReportRow {
+                name: "0".to_string(),
+                precision: 2.0 / 3.0,
+                recall: 1.0,
+                f1_score: 0.8,
+                support: 2,
+            }
+        );
+        assert_eq!(report.classes[1].f1_score, 0.0);
+        assert_float_relative_eq!(report.accuracy, 1.0 / 3.0, 0.001);
+        assert_float_relative_eq!(report.macro_avg.precision, 2.0 / 9.0, 0.001);
+        assert_float_relative_eq!(report.macro_avg.f1_score, 4.0 / 15.0, 0.001);
+        assert_float_relative_eq!(report.weighted_avg.recall, 1.0 / 3.0, 0.001);
+        assert_eq!(report.weighted_avg.support, 6);
+    }
+
+    #[rstest]
+    fn test_display() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 0, 0, 1],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 0, 1, 1],
+        };
+        let expected = "              precision     recall   f1-score    support
+
+           0       1.00       0.67       0.80          3
+           1       0.50       1.00       0.67          1
+
+    accuracy                             0.75          4
+   macro avg       0.75       0.83       0.73          4
+weighted avg       0.88       0.75       0.77          4
+";
+        assert_eq!(
+            classification_report(y_true, y_pred).unwrap().to_string(),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_mismatch_error(target_named_array: NamedArray<i32>) {
+        let short = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 1],
+        };
+        assert!(classification_report(target_named_array, short).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+use crate::clustering::{entropy, labeling_checks, Contingency};
+
+// how the two entropies are combined when normalising mutual information
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AverageMethod {
+    Min,
+    Geometric,
+    Arithmetic,
+    Max,
+}
+
+impl AverageMethod {
+    pub fn combine(&self, h_true: f64, h_pred: f64) -> f64 {
+        match self {
+            AverageMethod::Min => h_true.min(h_pred),
+            AverageMethod::Geometric => (h_true * h_pred).sqrt(),
+            AverageMethod::Arithmetic => (h_true + h_pred) / 2.0,
+            AverageMethod::Max => h_true.max(h_pred),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HomogeneityCompletenessVMeasure {
+    pub homogeneity: f64,
+    pub completeness: f64,
+    pub v_measure: f64,
+}
+
+fn pairs(n: f64) -> f64 {
+    n * (n - 1.0) / 2.0
+}
+
+fn mutual_info(contingency: &Contingency) -> f64 {
+    let n = contingency.n_rows;
+    contingency
+        .cells()
+        .map(|(i, j, n_ij)| {
+            n_ij / n * (n * n_ij / (contingency.class_sizes[i] * contingency.cluster_sizes[j])).ln()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+// mutual information expected between two random labelings with the same
+// cluster sizes (Vinh, Epps and Bailey, 2010)
+fn expected_mutual_info(contingency: &Contingency) -> f64 {
+    let n_rows = contingency.n_rows as usize;
+    let mut ln_factorial = vec![0.0; n_rows + 1];
+    for k in 1..=n_rows {
+        ln_factorial[k] = ln_factorial[k - 1] + (k as f64).ln();
+    }
+    let n = contingency.n_rows;
+
+    let mut emi = 0.0;
+    for a in contingency.class_sizes.iter().map(|s| *s as usize) {
+        for b in contingency.cluster_sizes.iter().map(|s| *s as usize) {
+            let start = (a + b).saturating_sub(n_rows).max(1);
+            for n_ij in start..=a.min(b) {
+                let ln_probability = ln_factorial[a]
+                    + ln_factorial[b]
+                    + ln_factorial[n_rows - a]
+                    + ln_factorial[n_rows - b]
+                    - ln_factorial[n_rows]
+                    - ln_factorial[n_ij]
+                    - ln_factorial[a - n_ij]
+                    - ln_factorial[b - n_ij]
+                    - ln_factorial[n_rows + n_ij - a - b];
+                let n_ij = n_ij as f64;
+                emi += n_ij / n * (n * n_ij / (a as f64 * b as f64)).ln() * ln_probability.exp();
+            }
+        }
+    }
+    emi
+}
+
+pub fn adjusted_rand_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (labels_true, labels_pred) = labeling_checks(&labels_true, &labels_pred)?;
+    let contingency = Contingency::new(&labels_true, &labels_pred);
+    // a single row has no pairs to compare, and any labelings of it agree
+    if contingency.n_rows < 2.0 {
+        return Ok(1.0);
+    }
+    let index: f64 = contingency.cells().map(|(_, _, n_ij)| pairs(n_ij)).sum();
+    let class_pairs: f64 = contingency.class_sizes.iter().map(|s| pairs(*s)).sum();
+    let cluster_pairs: f64 = contingency.cluster_sizes.iter().map(|s| pairs(*s)).sum();
+    let expected = class_pairs * cluster_pairs / pairs(contingency.n_rows);
+    let max_index = (class_pairs + cluster_pairs) / 2.0;
+    // both labelings a single cluster, or both all singletons
+    if max_index == expected {
+        return Ok(1.0);
+    }
+    Ok((index - expected) / (max_index - expected))
+}
+
+pub fn mutual_info_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (labels_true, labels_pred) = labeling_checks(&labels_true, &labels_pred)?;
+    Ok(mutual_info(&Contingency::new(&labels_true, &labels_pred)))
+}
+
+pub fn normalized_mutual_info_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+    average_method: AverageMethod,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (labels_true, labels_pred) = labeling_checks(&labels_true, &labels_pred)?;
+    let contingency = Contingency::new(&labels_true, &labels_pred);
+    let (h_true, h_pred) = (
+        entropy(&contingency.class_sizes),
+        entropy(&contingency.cluster_sizes),
+    );
+    // two single-cluster labelings agree perfectly
+    if h_true == 0.0 && h_pred == 0.0 {
+        return Ok(1.0);
+    }
+    let normalizer = average_method.combine(h_true, h_pred);
+    if normalizer == 0.0 {
+        return Ok(0.0);
+    }
+    Ok(mutual_info(&contingency) / normalizer)
+}
+
+pub fn adjusted_mutual_info_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+    average_method: AverageMethod,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (labels_true, labels_pred) = labeling_checks(&labels_true, &labels_pred)?;
+    let contingency = Contingency::new(&labels_true, &labels_pred);
+    let (h_true, h_pred) = (
+        entropy(&contingency.class_sizes),
+        entropy(&contingency.cluster_sizes),
+    );
+    if h_true == 0.0 && h_pred == 0.0 {
+        return Ok(1.0);
+    }
+    let emi = expected_mutual_info(&contingency);
+    // keep the denominator away from zero without flipping its sign
+    let denominator = average_method.combine(h_true, h_pred) - emi;
+    let denominator = if denominator < 0.0 {
+        denominator.min(-f64::EPSILON)
+    } else {
+        denominator.max(f64::EPSILON)
+    };
+    Ok((mutual_info(&contingency) - emi) / denominator)
+}
+
+// beta above 1 weights completeness more, below 1 homogeneity
+pub fn homogeneity_completeness_v_measure<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+    beta: f64,
+) -> Result<HomogeneityCompletenessVMeasure, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (labels_true, labels_pred) = labeling_checks(&labels_true, &labels_pred)?;
+    let contingency = Contingency::new(&labels_true, &labels_pred);
+    let mi = mutual_info(&contingency);
+    let (h_true, h_pred) = (
+        entropy(&contingency.class_sizes),
+        entropy(&contingency.cluster_sizes),
+    );
+    let homogeneity = if h_true == 0.0 { 1.0 } else { mi / h_true };
+    let completeness = if h_pred == 0.0 { 1.0 } else { mi / h_pred };
+    let v_measure = if homogeneity + completeness == 0.0 {
+        0.0
+    } else {
+        (1.0 + beta) * homogeneity * completeness / (beta * homogeneity + completeness)
+    };
+    Ok(HomogeneityCompletenessVMeasure {
+        homogeneity,
+        completeness,
+        v_measure,
+    })
+}
+
+pub fn homogeneity_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    Ok(homogeneity_completeness_v_measure(labels_true, labels_pred, 1.0)?.homogeneity)
+}
+
+pub fn completeness_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    Ok(homogeneity_completeness_v_measure(labels_true, labels_pred, 1.0)?.completeness)
+}
+
+pub fn v_measure_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+    beta: f64,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    Ok(homogeneity_completeness_v_measure(labels_true, labels_pred, beta)?.v_measure)
+}
+
+pub fn fowlkes_mallows_score<T>(
+    labels_true: NamedArray<T>,
+    labels_pred: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (labels_true, labels_pred) = labeling_checks(&labels_true, &labels_pred)?;
+    let contingency = Contingency::new(&labels_true, &labels_pred);
+    let n = contingency.n_rows;
+    let squares = |sizes: &[f64]| sizes.iter().map(|s| s * s).sum::<f64>() - n;
+    let tk = contingency
+        .cells()
+        .map(|(_, _, n_ij)| n_ij * n_ij)
+        .sum::<f64>()
+        - n;
+    if tk == 0.0 {
+        return Ok(0.0);
+    }
+    Ok(tk / (squares(&contingency.cluster_sizes) * squares(&contingency.class_sizes)).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    fn labels(data: Vec<i32>) -> NamedArray<i32> {
+        NamedArray {
+            name: "labels".to_string(),
+            data,
+        }
+    }
+
+    #[fixture]
+    fn labels_true() -> NamedArray<i32> {
+        labels(vec![0, 0, 0, 1, 1, 1])
+    }
+
+    #[fixture]
+    fn labels_pred() -> NamedArray<i32> {
+        labels(vec![0, 0, 1, 1, 2, 2])
+    }
+
+    #[rstest]
+    fn test_adjusted_rand(labels_true: NamedArray<i32>, labels_pred: NamedArray<i32>) {
+        assert_float_relative_eq!(
+            adjusted_rand_score(labels_true.clone(), labels_pred).unwrap(),
+            8.0 / 33.0,
+            0.001
+        );
+        // renaming clusters does not matter
+        assert_eq!(
+            adjusted_rand_score(labels_true, labels(vec![5, 5, 5, 2, 2, 2])).unwrap(),
+            1.0
+        );
+        assert_float_relative_eq!(
+            adjusted_rand_score(labels(vec![0, 0, 1, 1]), labels(vec![0, 1, 0, 1])).unwrap(),
+            -0.5,
+            0.001
+        );
+        assert_eq!(
+            adjusted_rand_score(labels(vec![0, 0, 0, 0]), labels(vec![0, 1, 2, 3])).unwrap(),
+            0.0
+        );
+    }
+
+    #[rstest]
+    fn test_mutual_info(labels_true: NamedArray<i32>, labels_pred: NamedArray<i32>) {
+        assert_float_relative_eq!(
+            mutual_info_score(labels_true.clone(), labels_pred.clone()).unwrap(),
+            0.462098,
+            0.001
+        );
+        assert_float_relative_eq!(
+            normalized_mutual_info_score(
+                labels_true.clone(),
+                labels_pred.clone(),
+                AverageMethod::Arithmetic
+            )
+            .unwrap(),
+            0.515804,
+            0.001
+        );
+        // checked against the mean over all 720 permutations of labels_pred
+        assert_float_relative_eq!(
+            adjusted_mutual_info_score(labels_true, labels_pred, AverageMethod::Arithmetic)
+                .unwrap(),
+            0.298792,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_identical_labelings(labels_true: NamedArray<i32>) {
+        for method in [
+            AverageMethod::Min,
+            AverageMethod::Geometric,
+            AverageMethod::Arithmetic,
+            AverageMethod::Max,
+        ] {
+            assert_float_relative_eq!(
+                adjusted_mutual_info_score(labels_true.clone(), labels_true.clone(), method)
+                    .unwrap(),
+                1.0,
+                0.001
+            );
+            assert_float_relative_eq!(
+                normalized_mutual_info_score(labels_true.clone(), labels_true.clone(), method)
+                    .unwrap(),
+                1.0,
+                0.001
+            );
+        }
+        let single = labels(vec![1; 4]);
+        assert_eq!(
+            adjusted_mutual_info_score(single.clone(), single, AverageMethod::Arithmetic).unwrap(),
+            1.0
+        );
+    }
+
+    #[rstest]
+    fn test_homogeneity_completeness(labels_true: NamedArray<i32>, labels_pred: NamedArray<i32>) {
+        let res = homogeneity_completeness_v_measure(labels_true.clone(), labels_pred.clone(), 1.0)
+            .unwrap();
+        assert_float_relative_eq!(res.homogeneity, 2.0 / 3.0, 0.001);
+        assert_float_relative_eq!(res.completeness, 0.420620, 0.001);
+        // v-measure with beta 1 is the arithmetic normalized mutual information
+        assert_float_relative_eq!(res.v_measure, 0.515804, 0.001);
+        assert_eq!(
+            homogeneity_score(labels_true.clone(), labels_pred.clone()).unwrap(),
+            res.homogeneity
+        );
+        assert_eq!(
+            completeness_score(labels_true, labels_pred).unwrap(),
+            res.completeness
+        );
+
+        // splitting a class is homogeneous but incomplete
+        let split = homogeneity_completeness_v_measure(
+            labels(vec![0, 0, 1, 1]),
+            labels(vec![0, 0, 1, 2]),
+            1.0,
+        )
+        .unwrap();
+        assert_eq!(split.homogeneity, 1.0);
+        assert_float_relative_eq!(split.completeness, 2.0 / 3.0, 0.001);
+        assert_float_relative_eq!(split.v_measure, 0.8, 0.001);
+    }
+
+    #[rstest]
+    fn test_fowlkes_mallows(labels_true: NamedArray<i32>, labels_pred: NamedArray<i32>) {
+        assert_float_relative_eq!(
+            fowlkes_mallows_score(labels_true.clone(), labels_pred).unwrap(),
+            0.471405,
+            0.001
+        );
+        assert_eq!(
+            fowlkes_mallows_score(labels_true.clone(), labels_true).unwrap(),
+            1.0
+        );
+        assert_eq!(
+            fowlkes_mallows_score(labels(vec![0, 0, 1, 1]), labels(vec![0, 1, 2, 3])).unwrap(),
+            0.0
+        );
+    }
+
+    #[rstest]
+    fn test_errors(labels_true: NamedArray<i32>) {
+        assert!(adjusted_rand_score(labels_true.clone(), labels(vec![0, 1])).is_err());
+        assert!(fowlkes_mallows_score(labels(vec![]), labels(vec![])).is_err());
+        let nan = NamedArray {
+            name: "labels".to_string(),
+            data: vec![0.0, f64::NAN],
+        };
+        let pred = NamedArray {
+            name: "labels".to_string(),
+            data: vec![0.0, 1.0],
+        };
+        assert!(adjusted_rand_score(nan.clone(), pred.clone()).is_err());
+        assert!(mutual_info_score(pred, nan).is_err());
+    }
+
+    #[rstest]
+    fn test_single_row() {
+        assert_eq!(
+            adjusted_rand_score(labels(vec![0]), labels(vec![3])).unwrap(),
+            1.0
+        );
+    }
+}
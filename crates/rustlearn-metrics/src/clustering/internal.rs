@@ -0,0 +1,286 @@
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+use crate::clustering::feature_checks;
+use crate::core::classification::unique_labels;
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+// rows of each cluster, in sorted label order
+fn cluster_rows(labels: &[f64]) -> Vec<Vec<usize>> {
+    unique_labels(labels, &[])
+        .iter()
+        .map(|label| (0..labels.len()).filter(|i| labels[*i] == *label).collect())
+        .collect()
+}
+
+fn centroid(points: &[Vec<f64>], rows: &[usize]) -> Vec<f64> {
+    let mut center = vec![0.0; points[0].len()];
+    for row in rows.iter() {
+        for (c, v) in center.iter_mut().zip(points[*row].iter()) {
+            *c += v;
+        }
+    }
+    center.iter().map(|c| c / rows.len() as f64).collect()
+}
+
+// x holds one NamedArray per feature; points in a cluster of their own
+// score 0
+pub fn silhouette_samples<T>(
+    x: Vec<NamedArray<T>>,
+    labels: NamedArray<T>,
+) -> Result<NamedArray<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (points, labels) = feature_checks(&x, &labels)?;
+    let clusters = cluster_rows(&labels);
+    let mut scores: Vec<f64> = Vec::new();
+    for (row, point) in points.iter().enumerate() {
+        let mut own = 0.0;
+        let mut nearest = f64::INFINITY;
+        for rows in clusters.iter() {
+            let total: f64 = rows.iter().map(|r| distance(point, &points[*r])).sum();
+            if rows.contains(&row) {
+                if rows.len() == 1 {
+                    own = f64::NAN;
+                } else {
+                    own = total / (rows.len() - 1) as f64;
+                }
+            } else {
+                nearest = nearest.min(total / rows.len() as f64);
+            }
+        }
+        // a point sitting on both its own cluster and the nearest other one
+        // is as ambiguous as a singleton, rather than 0 / 0
+        scores.push(if own.is_nan() || own.max(nearest) == 0.0 {
+            0.0
+        } else {
+            (nearest - own) / own.max(nearest)
+        });
+    }
+    Ok(NamedArray {
+        name: "silhouette".to_string(),
+        data: scores,
+    })
+}
+
+pub fn silhouette_score<T>(
+    x: Vec<NamedArray<T>>,
+    labels: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    silhouette_samples(x, labels)?.mean()
+}
+
+// between-cluster over within-cluster dispersion, each scaled by its
+// degrees of freedom
+pub fn calinski_harabasz_score<T>(
+    x: Vec<NamedArray<T>>,
+    labels: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (points, labels) = feature_checks(&x, &labels)?;
+    let clusters = cluster_rows(&labels);
+    let overall = centroid(&points, &(0..points.len()).collect::<Vec<usize>>());
+    let mut between = 0.0;
+    let mut within = 0.0;
+    for rows in clusters.iter() {
+        let center = centroid(&points, rows);
+        between += rows.len() as f64 * distance(&center, &overall).powi(2);
+        within += rows
+            .iter()
+            .map(|r| distance(&points[*r], &center).powi(2))
+            .sum::<f64>();
+    }
+    if within == 0.0 {
+        return Ok(1.0);
+    }
+    let (n_rows, n_clusters) = (points.len() as f64, clusters.len() as f64);
+    Ok(between * (n_rows - n_clusters) / (within * (n_clusters - 1.0)))
+}
+
+// the mean over clusters of the worst ratio of summed spread to centroid
+// separation; lower is better
+pub fn davies_bouldin_score<T>(
+    x: Vec<NamedArray<T>>,
+    labels: NamedArray<T>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (points, labels) = feature_checks(&x, &labels)?;
+    let clusters = cluster_rows(&labels);
+    let centers: Vec<Vec<f64>> = clusters
+        .iter()
+        .map(|rows| centroid(&points, rows))
+        .collect();
+    let spreads: Vec<f64> = clusters
+        .iter()
+        .zip(centers.iter())
+        .map(|(rows, center)| {
+            rows.iter()
+                .map(|r| distance(&points[*r], center))
+                .sum::<f64>()
+                / rows.len() as f64
+        })
+        .collect();
+    if spreads.iter().all(|s| *s == 0.0) {
+        return Ok(0.0);
+    }
+
+    let mut total = 0.0;
+    for i in 0..clusters.len() {
+        let worst = (0..clusters.len())
+            .filter(|j| *j != i)
+            .map(|j| distance(&centers[i], &centers[j]))
+            .zip((0..clusters.len()).filter(|j| *j != i))
+            .filter(|(separation, _)| *separation > 0.0)
+            .map(|(separation, j)| (spreads[i] + spreads[j]) / separation)
+            .fold(0.0, f64::max);
+        total += worst;
+    }
+    Ok(total / clusters.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "spend".to_string(),
+                data: vec![0.0, 0.0, 1.0, 4.0, 4.0, 5.0, 9.0],
+            },
+            NamedArray {
+                name: "visits".to_string(),
+                data: vec![0.0, 1.0, 0.0, 4.0, 5.0, 5.0, 0.0],
+            },
+        ]
+    }
+
+    #[fixture]
+    fn labels_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "segment".to_string(),
+            data: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0],
+        }
+    }
+
+    #[rstest]
+    fn test_silhouette(
+        input_named_array: Vec<NamedArray<f64>>,
+        labels_named_array: NamedArray<f64>,
+    ) {
+        let samples =
+            silhouette_samples(input_named_array.clone(), labels_named_array.clone()).unwrap();
+        let expected = [0.843187, 0.787730, 0.789874, 0.768707, 0.832317, 0.811482];
+        for (actual, expected) in samples.data.iter().zip(expected) {
+            assert_float_relative_eq!(*actual, expected, 0.001);
+        }
+        // the singleton cluster
+        assert_eq!(samples.data[6], 0.0);
+        assert_float_relative_eq!(
+            silhouette_score(input_named_array, labels_named_array).unwrap(),
+            0.690471,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_calinski_harabasz(
+        input_named_array: Vec<NamedArray<f64>>,
+        labels_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            calinski_harabasz_score(input_named_array, labels_named_array).unwrap(),
+            71.714286,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_davies_bouldin(
+        input_named_array: Vec<NamedArray<f64>>,
+        labels_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            davies_bouldin_score(input_named_array, labels_named_array).unwrap(),
+            0.180908,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_errors(input_named_array: Vec<NamedArray<f64>>) {
+        let one_cluster = NamedArray {
+            name: "segment".to_string(),
+            data: vec![0.0; 7],
+        };
+        assert!(silhouette_score(input_named_array.clone(), one_cluster).is_err());
+        let all_singletons = NamedArray {
+            name: "segment".to_string(),
+            data: (0..7).map(|i| i as f64).collect(),
+        };
+        assert!(calinski_harabasz_score(input_named_array.clone(), all_singletons).is_err());
+        let short = NamedArray {
+            name: "segment".to_string(),
+            data: vec![0.0, 1.0],
+        };
+        assert!(davies_bouldin_score(input_named_array.clone(), short).is_err());
+        let nan = NamedArray {
+            name: "segment".to_string(),
+            data: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, f64::NAN],
+        };
+        assert!(silhouette_score(input_named_array.clone(), nan).is_err());
+        assert!(silhouette_score(
+            vec![],
+            NamedArray {
+                name: "segment".to_string(),
+                data: vec![0.0, 1.0, 1.0],
+            }
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_silhouette_coincident_clusters() {
+        // the first four points coincide but sit in two clusters, so both
+        // distances are 0 for them
+        let x = vec![NamedArray {
+            name: "spend".to_string(),
+            data: vec![0.0, 0.0, 0.0, 0.0, 5.0, 5.0],
+        }];
+        let labels = NamedArray {
+            name: "segment".to_string(),
+            data: vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0],
+        };
+        assert_eq!(
+            silhouette_samples(x, labels).unwrap().data,
+            vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+use crate::core::classification::{finite_label_checks, labels_f64, unique_labels};
+
+pub mod external;
+pub mod internal;
+
+// counts of rows shared by every (true class, predicted cluster) pair
+#[derive(Debug, PartialEq, Clone)]
+pub struct Contingency {
+    pub counts: Vec<Vec<f64>>,
+    pub class_sizes: Vec<f64>,
+    pub cluster_sizes: Vec<f64>,
+    pub n_rows: f64,
+}
+
+impl Contingency {
+    pub fn new(labels_true: &[f64], labels_pred: &[f64]) -> Self {
+        let classes = unique_labels(labels_true, &[]);
+        let clusters = unique_labels(labels_pred, &[]);
+        let mut counts = vec![vec![0.0; clusters.len()]; classes.len()];
+        for (t, p) in labels_true.iter().zip(labels_pred.iter()) {
+            let i = classes.binary_search_by(|c| c.total_cmp(t)).unwrap();
+            let j = clusters.binary_search_by(|c| c.total_cmp(p)).unwrap();
+            counts[i][j] += 1.0;
+        }
+        Contingency {
+            class_sizes: counts.iter().map(|row| row.iter().sum()).collect(),
+            cluster_sizes: (0..clusters.len())
+                .map(|j| counts.iter().map(|row| row[j]).sum())
+                .collect(),
+            n_rows: labels_true.len() as f64,
+            counts,
+        }
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.counts.iter().enumerate().flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, n)| **n > 0.0)
+                .map(move |(j, n)| (i, j, *n))
+        })
+    }
+}
+
+pub fn entropy(sizes: &[f64]) -> f64 {
+    let n: f64 = sizes.iter().sum();
+    -sizes
+        .iter()
+        .filter(|s| **s > 0.0)
+        .map(|s| s / n * (s / n).ln())
+        .sum::<f64>()
+}
+
+pub fn labeling_checks<T>(
+    labels_true: &NamedArray<T>,
+    labels_pred: &NamedArray<T>,
+) -> Result<(Vec<f64>, Vec<f64>), RustLearnError>
+where
+    T: Num + ToPrimitive + Copy,
+{
+    if labels_true.len() != labels_pred.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "series are not the same length",
+        )));
+    }
+    if labels_true.is_empty() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "series are empty",
+        )));
+    }
+    let (labels_true, labels_pred) = (labels_f64(labels_true), labels_f64(labels_pred));
+    finite_label_checks(&labels_true)?;
+    finite_label_checks(&labels_pred)?;
+    Ok((labels_true, labels_pred))
+}
+
+// rows as points, with the number of distinct labels between 2 and n - 1
+pub fn feature_checks<T>(
+    x: &[NamedArray<T>],
+    labels: &NamedArray<T>,
+) -> Result<(Vec<Vec<f64>>, Vec<f64>), RustLearnError>
+where
+    T: Num + ToPrimitive + Copy,
+{
+    if x.is_empty() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "no features provided",
+        )));
+    }
+    if x.iter().any(|column| column.len() != labels.len()) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "features and labels are not the same length",
+        )));
+    }
+    let labels = labels_f64(labels);
+    finite_label_checks(&labels)?;
+    let n_labels = unique_labels(&labels, &[]).len();
+    if n_labels < 2 || n_labels >= labels.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(format!(
+            "number of labels is {n_labels}, it must be between 2 and the number of rows minus 1"
+        ))));
+    }
+    let points = (0..labels.len())
+        .map(|row| {
+            x.iter()
+                .map(|column| column.data[row].to_f64().unwrap())
+                .collect()
+        })
+        .collect();
+    Ok((points, labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[rstest]
+    fn test_contingency() {
+        let contingency = Contingency::new(&[0.0, 0.0, 1.0, 1.0], &[0.0, 0.0, 1.0, 2.0]);
+        assert_eq!(
+            contingency.counts,
+            vec![vec![2.0, 0.0, 0.0], vec![0.0, 1.0, 1.0]]
+        );
+        assert_eq!(contingency.class_sizes, vec![2.0, 2.0]);
+        assert_eq!(contingency.cluster_sizes, vec![2.0, 1.0, 1.0]);
+        assert_eq!(contingency.cells().count(), 3);
+    }
+
+    #[rstest]
+    fn test_entropy() {
+        assert_eq!(entropy(&[4.0]), 0.0);
+        assert_float_relative_eq!(entropy(&[2.0, 1.0, 1.0]), 1.5 * 2.0_f64.ln(), 0.001);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::core::classification::{classification_checks, safe_divide, unique_labels};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Normalize {
+    // each row sums to one, so the diagonal holds per-class recall
+    True,
+    // each column sums to one, so the diagonal holds per-class precision
+    Pred,
+    All,
+}
+
+// rows are the true labels and columns the predicted labels, both in the
+// order of `labels`; `names` defaults to the labels themselves
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConfusionMatrix {
+    pub labels: Vec<f64>,
+    pub names: Vec<String>,
+    pub matrix: Vec<Vec<f64>>,
+    pub normalize: Option<Normalize>,
+}
+
+impl ConfusionMatrix {
+    // `matrix` holds raw counts in the order of `labels`
+    pub fn from_counts(
+        labels: Vec<f64>,
+        mut matrix: Vec<Vec<f64>>,
+        normalize: Option<Normalize>,
+    ) -> Self {
+        match normalize {
+            Some(Normalize::True) => {
+                for row in matrix.iter_mut() {
+                    let total: f64 = row.iter().sum();
+                    row.iter_mut().for_each(|v| *v = safe_divide(*v, total));
+                }
+            }
+            Some(Normalize::Pred) => {
+                for col in 0..labels.len() {
+                    let total: f64 = matrix.iter().map(|row| row[col]).sum();
+                    matrix
+                        .iter_mut()
+                        .for_each(|row| row[col] = safe_divide(row[col], total));
+                }
+            }
+            Some(Normalize::All) => {
+                let total: f64 = matrix.iter().flatten().sum();
+                matrix
+                    .iter_mut()
+                    .flat_map(|row| row.iter_mut())
+                    .for_each(|v| *v = safe_divide(*v, total));
+            }
+            None => {}
+        }
+
+        ConfusionMatrix {
+            names: labels.iter().map(|l| l.to_string()).collect(),
+            labels,
+            matrix,
+            normalize,
+        }
+    }
+
+    pub fn with_names(self, names: Vec<String>) -> Result<Self, RustLearnError> {
+        if names.len() != self.labels.len() {
+            return Err(RustLearnError::ValidationError(ErrString::from(format!(
+                "expected {} class names, got {}",
+                self.labels.len(),
+                names.len()
+            ))));
+        }
+        Ok(ConfusionMatrix { names, ..self })
+    }
+
+    pub fn get(&self, true_label: f64, pred_label: f64) -> Option<f64> {
+        let row = self.labels.iter().position(|l| *l == true_label)?;
+        let col = self.labels.iter().position(|l| *l == pred_label)?;
+        Some(self.matrix[row][col])
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        match self.normalize {
+            Some(_) => format!("{value:.2}"),
+            None => format!("{value:.0}"),
+        }
+    }
+}
+
+impl Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let cells: Vec<Vec<String>> = self
+            .matrix
+            .iter()
+            .map(|row| row.iter().map(|v| self.format_value(*v)).collect())
+            .collect();
+        let label_width = self
+            .names
+            .iter()
+            .map(|n| n.len())
+            .max()
+            .unwrap_or(0)
+            .max("true\\pred".len());
+        let widths: Vec<usize> = (0..self.names.len())
+            .map(|col| {
+                cells
+                    .iter()
+                    .map(|row| row[col].len())
+                    .max()
+                    .unwrap_or(0)
+                    .max(self.names[col].len())
+            })
+            .collect();
+
+        write!(f, "{:<label_width$}", "true\\pred")?;
+        for (name, width) in self.names.iter().zip(widths.iter()) {
+            write!(f, "  {name:>width$}")?;
+        }
+        for (name, row) in self.names.iter().zip(cells.iter()) {
+            write!(f, "\n{name:<label_width$}")?;
+            for (cell, width) in row.iter().zip(widths.iter()) {
+                write!(f, "  {cell:>width$}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn confusion_matrix<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    normalize: Option<Normalize>,
+) -> Result<ConfusionMatrix, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let labels = unique_labels(&y_true, &y_pred);
+    let position = |value: &f64| labels.binary_search_by(|l| l.total_cmp(value)).unwrap();
+
+    let mut matrix = vec![vec![0.0; labels.len()]; labels.len()];
+    for (t, p) in y_true.iter().zip(y_pred.iter()) {
+        matrix[position(t)][position(p)] += 1.0;
+    }
+
+    Ok(ConfusionMatrix::from_counts(labels, matrix, normalize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 2, 0, 1, 2],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1, 0, 0, 1],
+        }
+    }
+
+    #[rstest]
+    fn test_confusion_matrix(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array: NamedArray<i32>,
+    ) {
+        let cm = confusion_matrix(target_named_array, predictions_named_array, None).unwrap();
+        assert_eq!(cm.labels, vec![0.0, 1.0, 2.0]);
+        assert_eq!(cm.names, vec!["0", "1", "2"]);
+        assert_eq!(
+            cm.matrix,
+            vec![
+                vec![2.0, 0.0, 0.0],
+                vec![1.0, 0.0, 1.0],
+                vec![0.0, 2.0, 0.0]
+            ]
+        );
+        assert_eq!(cm.get(1.0, 2.0), Some(1.0));
+        assert_eq!(cm.get(3.0, 2.0), None);
+    }
+
+    #[rstest]
+    fn test_normalize(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array: NamedArray<i32>,
+    ) {
+        let by_true = confusion_matrix(
+            target_named_array.clone(),
+            predictions_named_array.clone(),
+            Some(Normalize::True),
+        )
+        .unwrap();
+        assert_eq!(by_true.matrix[1], vec![0.5, 0.0, 0.5]);
+
+        let by_pred = confusion_matrix(
+            target_named_array.clone(),
+            predictions_named_array.clone(),
+            Some(Normalize::Pred),
+        )
+        .unwrap();
+        assert_float_relative_eq!(by_pred.matrix[0][0], 2.0 / 3.0, 0.001);
+        assert_float_relative_eq!(by_pred.matrix[1][0], 1.0 / 3.0, 0.001);
+
+        let all = confusion_matrix(
+            target_named_array,
+            predictions_named_array,
+            Some(Normalize::All),
+        )
+        .unwrap();
+        let total: f64 = all.matrix.iter().flatten().sum();
+        assert_float_relative_eq!(total, 1.0, 0.001);
+    }
+
+    #[rstest]
+    fn test_display(target_named_array: NamedArray<i32>, predictions_named_array: NamedArray<i32>) {
+        let cm = confusion_matrix(target_named_array, predictions_named_array, None)
+            .unwrap()
+            .with_names(vec![
+                "cat".to_string(),
+                "dog".to_string(),
+                "rabbit".to_string(),
+            ])
+            .unwrap();
+        let expected = "\
+true\\pred  cat  dog  rabbit
+cat          2    0       0
+dog          1    0       1
+rabbit       0    2       0";
+        assert_eq!(cm.to_string(), expected);
+    }
+
+    #[rstest]
+    fn test_errors(target_named_array: NamedArray<i32>, predictions_named_array: NamedArray<i32>) {
+        let cm =
+            confusion_matrix(target_named_array.clone(), predictions_named_array, None).unwrap();
+        assert!(cm.with_names(vec!["cat".to_string()]).is_err());
+        let short = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 1],
+        };
+        assert!(confusion_matrix(target_named_array, short, None).is_err());
+    }
+
+    #[rstest]
+    fn test_nan_label() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, f64::NAN],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0.0, 1.0],
+        };
+        assert!(confusion_matrix(y_true.clone(), y_pred.clone(), None).is_err());
+        assert!(confusion_matrix(y_pred, y_true, None).is_err());
+    }
+}
@@ -15,3 +15,16 @@ where
     }
     None
 }
+
+// the same validation as `checks` without taking ownership of the series
+pub fn length_checks<T>(
+    y_true: &NamedArray<T>,
+    y_pred: &NamedArray<T>,
+) -> Result<(), RustLearnError> {
+    if y_true.data.len() != y_pred.data.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "series are not the same length",
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+use crate::core::base::checks;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Average {
+    // scores the positive label 1 only
+    Binary,
+    Micro,
+    Macro,
+    Weighted,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassCounts {
+    pub label: f64,
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub support: usize,
+}
+
+pub fn labels_f64<T>(named_array: &NamedArray<T>) -> Vec<f64>
+where
+    T: ToPrimitive,
+{
+    named_array
+        .data
+        .iter()
+        .map(|x| x.to_f64().unwrap())
+        .collect()
+}
+
+// every label seen in either series, sorted
+pub fn unique_labels(y_true: &[f64], y_pred: &[f64]) -> Vec<f64> {
+    let mut labels: Vec<f64> = y_true.iter().chain(y_pred.iter()).copied().collect();
+    labels.sort_by(|a, b| a.total_cmp(b));
+    labels.dedup();
+    labels
+}
+
+// NaN never equals itself, so it cannot be matched up as a label
+pub fn finite_label_checks(labels: &[f64]) -> Result<(), RustLearnError> {
+    if labels.iter().any(|l| !l.is_finite()) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "labels must be finite",
+        )));
+    }
+    Ok(())
+}
+
+pub fn classification_checks<T>(
+    y_true: &NamedArray<T>,
+    y_pred: &NamedArray<T>,
+) -> Result<(Vec<f64>, Vec<f64>), RustLearnError>
+where
+    T: Num + ToPrimitive + Copy,
+{
+    if let Some(checked) = checks(y_true.clone(), y_pred.clone()) {
+        return Err(checked);
+    }
+    if y_true.data.is_empty() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "series are empty",
+        )));
+    }
+    let (y_true, y_pred) = (labels_f64(y_true), labels_f64(y_pred));
+    finite_label_checks(&y_true)?;
+    finite_label_checks(&y_pred)?;
+    Ok((y_true, y_pred))
+}
+
+pub fn class_counts(y_true: &[f64], y_pred: &[f64]) -> Vec<ClassCounts> {
+    unique_labels(y_true, y_pred)
+        .into_iter()
+        .map(|label| {
+            let mut counts = ClassCounts {
+                label,
+                true_positives: 0,
+                false_positives: 0,
+                false_negatives: 0,
+                support: 0,
+            };
+            for (t, p) in y_true.iter().zip(y_pred.iter()) {
+                match (*t == label, *p == label) {
+                    (true, true) => counts.true_positives += 1,
+                    (false, true) => counts.false_positives += 1,
+                    (true, false) => counts.false_negatives += 1,
+                    (false, false) => {}
+                }
+                if *t == label {
+                    counts.support += 1;
+                }
+            }
+            counts
+        })
+        .collect()
+}
+
+// zero when the denominator is, rather than NaN
+pub fn safe_divide(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
+// reduces a per-class score under the requested averaging; `score` maps
+// (true positives, false positives, false negatives) to a value
+pub fn average_score<F>(
+    counts: &[ClassCounts],
+    average: Average,
+    score: F,
+) -> Result<f64, RustLearnError>
+where
+    F: Fn(f64, f64, f64) -> f64,
+{
+    let per_class = |c: &ClassCounts| {
+        score(
+            c.true_positives as f64,
+            c.false_positives as f64,
+            c.false_negatives as f64,
+        )
+    };
+    match average {
+        Average::Binary => {
+            if counts.len() > 2 || counts.iter().any(|c| c.label != 0.0 && c.label != 1.0) {
+                return Err(RustLearnError::ValidationError(ErrString::from(
+                    "binary averaging needs labels 0 and 1, use micro, macro or weighted",
+                )));
+            }
+            Ok(counts
+                .iter()
+                .find(|c| c.label == 1.0)
+                .map(per_class)
+                .unwrap_or(0.0))
+        }
+        Average::Micro => {
+            let tp: usize = counts.iter().map(|c| c.true_positives).sum();
+            let fp: usize = counts.iter().map(|c| c.false_positives).sum();
+            let fn_: usize = counts.iter().map(|c| c.false_negatives).sum();
+            Ok(score(tp as f64, fp as f64, fn_ as f64))
+        }
+        Average::Macro => Ok(counts.iter().map(per_class).sum::<f64>() / counts.len() as f64),
+        Average::Weighted => {
+            let total: usize = counts.iter().map(|c| c.support).sum();
+            Ok(safe_divide(
+                counts
+                    .iter()
+                    .map(|c| per_class(c) * c.support as f64)
+                    .sum::<f64>(),
+                total as f64,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn test_class_counts() {
+        let counts = class_counts(&[0.0, 1.0, 2.0, 1.0], &[0.0, 2.0, 2.0, 1.0]);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(
+            counts[2],
+            ClassCounts {
+                label: 2.0,
+                true_positives: 1,
+                false_positives: 1,
+                false_negatives: 0,
+                support: 1,
+            }
+        );
+        assert_eq!(counts[1].false_negatives, 1);
+        assert_eq!(counts[1].support, 2);
+    }
+
+    #[rstest]
+    fn test_binary_needs_two_labels() {
+        let counts = class_counts(&[0.0, 1.0, 2.0], &[0.0, 1.0, 2.0]);
+        assert!(average_score(&counts, Average::Binary, |tp, _, _| tp).is_err());
+    }
+}
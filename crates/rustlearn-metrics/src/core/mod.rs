@@ -1 +1,4 @@
 pub mod base;
+pub mod classification;
+pub mod thresholds;
+pub mod weights;
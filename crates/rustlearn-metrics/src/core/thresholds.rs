@@ -0,0 +1,112 @@
+use crate::core::classification::labels_f64;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+// cumulative counts when every row scoring at or above the threshold is
+// called positive; thresholds are the distinct scores, descending, so tied
+// scores always move together
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThresholdCounts {
+    pub thresholds: Vec<f64>,
+    pub false_positives: Vec<f64>,
+    pub true_positives: Vec<f64>,
+}
+
+impl ThresholdCounts {
+    pub fn positives(&self) -> f64 {
+        *self.true_positives.last().unwrap()
+    }
+
+    pub fn negatives(&self) -> f64 {
+        *self.false_positives.last().unwrap()
+    }
+}
+
+// labels can be any numeric type while scores are always continuous
+pub fn score_checks<T>(
+    y_true: &NamedArray<T>,
+    y_score: &NamedArray<f64>,
+) -> Result<(Vec<f64>, Vec<f64>), RustLearnError>
+where
+    T: ToPrimitive,
+{
+    if y_true.data.len() != y_score.data.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "series are not the same length",
+        )));
+    }
+    if y_true.data.is_empty() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "series are empty",
+        )));
+    }
+    Ok((labels_f64(y_true), y_score.data.clone()))
+}
+
+pub fn binary_checks(y_true: &[f64], y_score: &[f64]) -> Result<(), RustLearnError> {
+    if y_true.iter().any(|y| *y != 0.0 && *y != 1.0) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "binary labels must be 0 or 1",
+        )));
+    }
+    if y_score.iter().any(|s| s.is_nan()) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "scores contain NaN",
+        )));
+    }
+    Ok(())
+}
+
+pub fn threshold_counts(
+    y_true: &[f64],
+    y_score: &[f64],
+) -> Result<ThresholdCounts, RustLearnError> {
+    binary_checks(y_true, y_score)?;
+    let mut order: Vec<usize> = (0..y_score.len()).collect();
+    order.sort_by(|a, b| y_score[*b].total_cmp(&y_score[*a]));
+
+    let mut counts = ThresholdCounts {
+        thresholds: Vec::new(),
+        false_positives: Vec::new(),
+        true_positives: Vec::new(),
+    };
+    let (mut fp, mut tp) = (0.0, 0.0);
+    for (i, row) in order.iter().enumerate() {
+        if y_true[*row] == 1.0 {
+            tp += 1.0;
+        } else {
+            fp += 1.0;
+        }
+        // only record once the last row of a run of tied scores is counted
+        let last_of_tie = i + 1 == order.len() || y_score[order[i + 1]] != y_score[*row];
+        if last_of_tie {
+            counts.thresholds.push(y_score[*row]);
+            counts.false_positives.push(fp);
+            counts.true_positives.push(tp);
+        }
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn test_threshold_counts() {
+        let counts = threshold_counts(&[0.0, 1.0, 1.0, 0.0], &[0.2, 0.5, 0.9, 0.5]).unwrap();
+        assert_eq!(counts.thresholds, vec![0.9, 0.5, 0.2]);
+        assert_eq!(counts.true_positives, vec![1.0, 2.0, 2.0]);
+        assert_eq!(counts.false_positives, vec![0.0, 1.0, 2.0]);
+        assert_eq!(counts.positives(), 2.0);
+        assert_eq!(counts.negatives(), 2.0);
+    }
+
+    #[rstest]
+    fn test_binary_checks() {
+        assert!(threshold_counts(&[0.0, 2.0], &[0.1, 0.2]).is_err());
+        assert!(threshold_counts(&[0.0, 1.0], &[0.1, f64::NAN]).is_err());
+    }
+}
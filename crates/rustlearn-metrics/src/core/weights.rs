@@ -0,0 +1,287 @@
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+use crate::core::base::checks;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MultiOutput {
+    // one score per output
+    RawValues,
+    UniformAverage,
+    // outputs weighted by the variance of their true values
+    VarianceWeighted,
+}
+
+// unit weights when none are given
+pub fn weight_checks(
+    sample_weight: &Option<NamedArray<f64>>,
+    n_rows: usize,
+) -> Result<Vec<f64>, RustLearnError> {
+    match sample_weight {
+        None => Ok(vec![1.0; n_rows]),
+        Some(weights) => {
+            if weights.len() != n_rows {
+                return Err(RustLearnError::ValidationError(ErrString::from(
+                    "sample weights are not the same length as the series",
+                )));
+            }
+            if weights.data.iter().any(|w| *w < 0.0 || w.is_nan()) {
+                return Err(RustLearnError::ValidationError(ErrString::from(
+                    "sample weights must be non-negative",
+                )));
+            }
+            if weights.data.iter().sum::<f64>() == 0.0 {
+                return Err(RustLearnError::ValidationError(ErrString::from(
+                    "sample weights sum to zero",
+                )));
+            }
+            Ok(weights.data.clone())
+        }
+    }
+}
+
+pub fn weighted_mean(values: &[f64], weights: &[f64]) -> f64 {
+    let total: f64 = values.iter().zip(weights.iter()).map(|(v, w)| v * w).sum();
+    total / weights.iter().sum::<f64>()
+}
+
+pub fn weighted_variance(values: &[f64], weights: &[f64]) -> f64 {
+    let mean = weighted_mean(values, weights);
+    let squares: Vec<f64> = values.iter().map(|v| (v - mean).powi(2)).collect();
+    weighted_mean(&squares, weights)
+}
+
+pub fn multioutput_checks<T>(
+    y_true: &[NamedArray<T>],
+    y_pred: &[NamedArray<T>],
+) -> Result<(), RustLearnError>
+where
+    T: Num + ToPrimitive + Copy,
+{
+    if y_true.is_empty() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "no outputs provided",
+        )));
+    }
+    if y_true.len() != y_pred.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "y_true and y_pred do not have the same number of outputs",
+        )));
+    }
+    for (t, p) in y_true.iter().zip(y_pred.iter()) {
+        if let Some(checked) = checks(t.clone(), p.clone()) {
+            return Err(checked);
+        }
+        if t.len() != y_true[0].len() {
+            return Err(RustLearnError::ValidationError(ErrString::from(
+                "outputs are not the same length",
+            )));
+        }
+    }
+    Ok(())
+}
+
+// the smallest value whose cumulative weight reaches q of the total, as
+// scikit-learn's weighted percentile does; unlike the interpolated quantile
+// it always returns one of the values
+pub fn weighted_percentile(values: &[f64], weights: &[f64], q: f64) -> f64 {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let target = q * weights.iter().sum::<f64>();
+    let mut cumulative = 0.0;
+    for i in order.iter() {
+        cumulative += weights[*i];
+        if cumulative >= target {
+            return values[*i];
+        }
+    }
+    values[*order.last().unwrap()]
+}
+
+// scores one output at a time and combines them; RawValues returns one score
+// per output, the averages a single score
+pub fn score_outputs<T, F>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+    score: F,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num + ToPrimitive + Copy,
+    F: Fn(NamedArray<T>, NamedArray<T>, Option<NamedArray<f64>>) -> Result<f64, RustLearnError>,
+{
+    multioutput_checks(&y_true, &y_pred)?;
+    let weights = weight_checks(&sample_weight, y_true[0].len())?;
+    let mut scores: Vec<f64> = Vec::new();
+    let mut variances: Vec<f64> = Vec::new();
+    for (t, p) in y_true.into_iter().zip(y_pred) {
+        let values: Vec<f64> = t.data.iter().map(|v| v.to_f64().unwrap()).collect();
+        variances.push(weighted_variance(&values, &weights));
+        scores.push(score(t, p, sample_weight.clone())?);
+    }
+
+    match multioutput {
+        MultiOutput::RawValues => Ok(scores),
+        MultiOutput::UniformAverage => Ok(vec![scores.iter().sum::<f64>() / scores.len() as f64]),
+        MultiOutput::VarianceWeighted => {
+            if variances.iter().sum::<f64>() == 0.0 {
+                return Err(RustLearnError::ValidationError(ErrString::from(
+                    "variance weighting needs at least one non-constant output",
+                )));
+            }
+            Ok(vec![weighted_mean(&scores, &variances)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn test_weight_checks() {
+        assert_eq!(weight_checks(&None, 3).unwrap(), vec![1.0; 3]);
+        let weights = |data: Vec<f64>| {
+            Some(NamedArray {
+                name: "weights".to_string(),
+                data,
+            })
+        };
+        assert_eq!(
+            weight_checks(&weights(vec![1.0, 2.0]), 2).unwrap(),
+            vec![1.0, 2.0]
+        );
+        assert!(weight_checks(&weights(vec![1.0, 2.0]), 3).is_err());
+        assert!(weight_checks(&weights(vec![1.0, -2.0]), 2).is_err());
+        assert!(weight_checks(&weights(vec![0.0, 0.0]), 2).is_err());
+    }
+
+    #[rstest]
+    fn test_weighted_variance() {
+        assert_eq!(weighted_mean(&[1.0, 4.0], &[2.0, 1.0]), 2.0);
+        assert_eq!(weighted_variance(&[1.0, 4.0], &[2.0, 1.0]), 2.0);
+    }
+
+    #[rstest]
+    fn test_weighted_percentile() {
+        let values = [4.0, 1.0, 3.0, 2.0];
+        assert_eq!(
+            weighted_percentile(&values, &[1.0, 1.0, 1.0, 1.0], 0.5),
+            2.0
+        );
+        assert_eq!(
+            weighted_percentile(&values, &[4.0, 1.0, 1.0, 1.0], 0.5),
+            4.0
+        );
+        assert_eq!(
+            weighted_percentile(&values, &[1.0, 1.0, 1.0, 1.0], 0.9),
+            4.0
+        );
+    }
+
+    #[fixture]
+    fn y_true() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "first".to_string(),
+                data: vec![1.0, 2.0, 3.0],
+            },
+            NamedArray {
+                name: "second".to_string(),
+                data: vec![0.0, 0.0, 6.0],
+            },
+        ]
+    }
+
+    #[fixture]
+    fn y_pred() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "first".to_string(),
+                data: vec![1.0, 2.0, 5.0],
+            },
+            NamedArray {
+                name: "second".to_string(),
+                data: vec![1.0, 0.0, 6.0],
+            },
+        ]
+    }
+
+    fn absolute_error(
+        y_true: NamedArray<f64>,
+        y_pred: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) -> Result<f64, RustLearnError> {
+        let weights = weight_checks(&sample_weight, y_true.len())?;
+        let errors: Vec<f64> = y_true
+            .data
+            .iter()
+            .zip(y_pred.data.iter())
+            .map(|(t, p)| (t - p).abs())
+            .collect();
+        Ok(weighted_mean(&errors, &weights))
+    }
+
+    #[rstest]
+    fn test_score_outputs(y_true: Vec<NamedArray<f64>>, y_pred: Vec<NamedArray<f64>>) {
+        let score = |multioutput, sample_weight| {
+            score_outputs(
+                y_true.clone(),
+                y_pred.clone(),
+                sample_weight,
+                multioutput,
+                absolute_error,
+            )
+        };
+        assert_eq!(
+            score(MultiOutput::RawValues, None).unwrap(),
+            vec![2.0 / 3.0, 1.0 / 3.0]
+        );
+        assert_eq!(score(MultiOutput::UniformAverage, None).unwrap(), vec![0.5]);
+        // true variances are 2/3 and 8
+        assert_eq!(
+            score(MultiOutput::VarianceWeighted, None).unwrap(),
+            vec![(2.0 / 3.0 * 2.0 / 3.0 + 8.0 / 3.0) / (2.0 / 3.0 + 8.0)]
+        );
+        let weights = NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0, 1.0, 2.0],
+        };
+        assert_eq!(
+            score(MultiOutput::RawValues, Some(weights)).unwrap(),
+            vec![1.0, 0.25]
+        );
+        let short = NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0],
+        };
+        assert!(score(MultiOutput::RawValues, Some(short)).is_err());
+    }
+
+    #[rstest]
+    fn test_score_outputs_errors(y_true: Vec<NamedArray<f64>>, y_pred: Vec<NamedArray<f64>>) {
+        assert!(score_outputs(
+            y_true.clone(),
+            y_pred[..1].to_vec(),
+            None,
+            MultiOutput::RawValues,
+            absolute_error
+        )
+        .is_err());
+        let constant = vec![NamedArray {
+            name: "first".to_string(),
+            data: vec![2.0, 2.0, 2.0],
+        }];
+        assert!(score_outputs(
+            constant,
+            y_pred[..1].to_vec(),
+            None,
+            MultiOutput::VarianceWeighted,
+            absolute_error
+        )
+        .is_err());
+    }
+}
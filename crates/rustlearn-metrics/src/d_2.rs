@@ -0,0 +1,244 @@
+use crate::core::base::checks;
+use crate::core::weights::{score_outputs, weight_checks, weighted_percentile, MultiOutput};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_array::stats::QuantileMethod;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+fn pinball_loss(residual: f64, alpha: f64) -> f64 {
+    if residual >= 0.0 {
+        alpha * residual
+    } else {
+        (alpha - 1.0) * residual
+    }
+}
+
+// the fraction of pinball loss explained relative to always predicting the
+// alpha quantile of y_true; a constant target scores 1 when predicted exactly
+// and 0 otherwise. With weights the baseline is the weighted percentile.
+pub fn d_2_pinball_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+    alpha: f64,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    if alpha <= 0.0 || alpha >= 1.0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "alpha must be between 0 and 1",
+        )));
+    }
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let weights = weight_checks(&sample_weight, y_true.len())?;
+            let baseline = match sample_weight {
+                None => y_true.quantile(alpha, QuantileMethod::Linear)?,
+                Some(_) => {
+                    let values: Vec<f64> =
+                        y_true.data.iter().map(|t| t.to_f64().unwrap()).collect();
+                    weighted_percentile(&values, &weights, alpha)
+                }
+            };
+            let mut numerator: f64 = 0.0;
+            let mut denominator: f64 = 0.0;
+            for ((t, p), w) in y_true.data.iter().zip(y_pred.data.iter()).zip(weights) {
+                let (t, p) = (t.to_f64().unwrap(), p.to_f64().unwrap());
+                numerator += w * pinball_loss(t - p, alpha);
+                denominator += w * pinball_loss(t - baseline, alpha);
+            }
+            if denominator == 0.0 {
+                return Ok(if numerator == 0.0 { 1.0 } else { 0.0 });
+            }
+            Ok(1.0 - numerator / denominator)
+        }
+    }
+}
+
+// the median baseline of d_2_pinball_score at alpha 0.5
+pub fn d_2_absolute_error_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    d_2_pinball_score(y_true, y_pred, sample_weight, 0.5)
+}
+
+pub fn d_2_pinball_score_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    alpha: f64,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(y_true, y_pred, sample_weight, multioutput, |t, p, w| {
+        d_2_pinball_score(t, p, w, alpha)
+    })
+}
+
+pub fn d_2_absolute_error_score_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        d_2_absolute_error_score,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![3.0, -0.5, 2.0, 7.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![2.5, 0.0, 2.0, 8.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array_missing() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[rstest]
+    fn test_equivalent(target_named_array: NamedArray<f64>) {
+        assert_eq!(
+            d_2_absolute_error_score(target_named_array.clone(), target_named_array.clone(), None)
+                .unwrap(),
+            1.0
+        );
+    }
+
+    #[rstest]
+    fn test_mismatch_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array_missing: NamedArray<f64>,
+    ) {
+        assert!(d_2_absolute_error_score(
+            target_named_array,
+            predictions_named_array_missing,
+            None
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_d_2_absolute_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            d_2_absolute_error_score(target_named_array, predictions_named_array, None).unwrap(),
+            1.0 - 2.0 / 8.5,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_d_2_pinball(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            d_2_pinball_score(
+                target_named_array.clone(),
+                predictions_named_array.clone(),
+                None,
+                0.9
+            )
+            .unwrap(),
+            1.0 - 0.6 / 2.37,
+            0.001
+        );
+        assert!(d_2_pinball_score(target_named_array, predictions_named_array, None, 1.0).is_err());
+    }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0, 1.0, 1.0, 4.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            d_2_absolute_error_score(target_named_array, predictions_named_array, sample_weight)
+                .unwrap(),
+            1.0 - 10.0 / 33.0,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = d_2_absolute_error_score_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 1.0 - 2.0 / 8.5, 0.001);
+        assert_eq!(raw[1], 1.0);
+        let raw = d_2_pinball_score_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            0.9,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 1.0 - 0.6 / 2.37, 0.001);
+        assert_eq!(raw[1], 1.0);
+    }
+}
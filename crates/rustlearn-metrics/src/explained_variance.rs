@@ -0,0 +1,187 @@
+use crate::core::base::checks;
+use crate::core::weights::{score_outputs, weight_checks, weighted_variance, MultiOutput};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+// like r_2 but ignores a constant bias in the predictions; a constant target
+// scores 1 when predicted exactly and 0 otherwise
+pub fn explained_variance_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let weights = weight_checks(&sample_weight, y_true.len())?;
+            let values: Vec<f64> = y_true.data.iter().map(|t| t.to_f64().unwrap()).collect();
+            let residuals: Vec<f64> = values
+                .iter()
+                .zip(y_pred.data.iter())
+                .map(|(t, p)| t - p.to_f64().unwrap())
+                .collect();
+            let residual_var = weighted_variance(&residuals, &weights);
+            let true_var = weighted_variance(&values, &weights);
+            if true_var == 0.0 {
+                return Ok(if residual_var == 0.0 { 1.0 } else { 0.0 });
+            }
+            Ok(1.0 - residual_var / true_var)
+        }
+    }
+}
+
+pub fn explained_variance_score_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        explained_variance_score,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![3.0, -0.5, 2.0, 7.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![2.5, 0.0, 2.0, 8.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array_missing() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[rstest]
+    fn test_equivalent(target_named_array: NamedArray<f64>) {
+        assert_eq!(
+            explained_variance_score(target_named_array.clone(), target_named_array.clone(), None)
+                .unwrap(),
+            1.0
+        );
+    }
+
+    #[rstest]
+    fn test_mismatch_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array_missing: NamedArray<f64>,
+    ) {
+        assert!(explained_variance_score(
+            target_named_array,
+            predictions_named_array_missing,
+            None
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_explained_variance(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            explained_variance_score(target_named_array, predictions_named_array, None).unwrap(),
+            0.957173,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_bias_ignored(target_named_array: NamedArray<f64>) {
+        let shifted = target_named_array.map("predictions", |v| v + 10.0);
+        assert_eq!(
+            explained_variance_score(target_named_array, shifted, None).unwrap(),
+            1.0
+        );
+    }
+
+    #[rstest]
+    fn test_constant_target() {
+        let constant = NamedArray {
+            name: "target".to_string(),
+            data: vec![2.0, 2.0, 2.0],
+        };
+        let off = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        };
+        assert_eq!(
+            explained_variance_score(constant.clone(), constant.clone(), None).unwrap(),
+            1.0
+        );
+        assert_eq!(explained_variance_score(constant, off, None).unwrap(), 0.0);
+    }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0, 1.0, 1.0, 4.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            explained_variance_score(target_named_array, predictions_named_array, sample_weight)
+                .unwrap(),
+            0.962056,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = explained_variance_score_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 0.957173, 0.001);
+        assert_eq!(raw[1], 1.0);
+    }
+}
@@ -1,4 +1,22 @@
+pub mod accumulators;
+pub mod accuracy;
+pub mod agreement;
+pub mod calibration;
+pub mod classification_report;
+pub mod clustering;
+pub mod confusion_matrix;
 pub mod core;
+pub mod d_2;
+pub mod explained_variance;
 pub mod mean_absolute_error;
+pub mod mean_absolute_percentage_error;
 pub mod mean_squared_error;
+pub mod mean_squared_log_error;
+pub mod median_absolute_error;
+pub mod precision_recall;
+pub mod precision_recall_curve;
+pub mod probability;
 pub mod r_2;
+pub mod ranking;
+pub mod roc;
+pub mod scorer;
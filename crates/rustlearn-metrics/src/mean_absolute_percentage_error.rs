@@ -0,0 +1,260 @@
+use crate::core::base::checks;
+use crate::core::weights::{score_outputs, weight_checks, weighted_mean, MultiOutput};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+// returned as a fraction rather than a percentage; true values of zero are
+// floored at machine epsilon so the error stays finite
+pub fn mean_absolute_percentage_error<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let weights = weight_checks(&sample_weight, y_true.len())?;
+            let mut errors: Vec<f64> = Vec::new();
+            for (t, p) in y_true.data.iter().zip(y_pred.data.iter()) {
+                let (t, p) = (t.to_f64().unwrap(), p.to_f64().unwrap());
+                errors.push((t - p).abs() / t.abs().max(f64::EPSILON));
+            }
+            Ok(weighted_mean(&errors, &weights))
+        }
+    }
+}
+
+pub fn mean_absolute_percentage_error_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        mean_absolute_percentage_error,
+    )
+}
+
+// bounded between 0 and 2; rows where both values are zero count as exact
+pub fn symmetric_mean_absolute_percentage_error<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let weights = weight_checks(&sample_weight, y_true.len())?;
+            let mut errors: Vec<f64> = Vec::new();
+            for (t, p) in y_true.data.iter().zip(y_pred.data.iter()) {
+                let (t, p) = (t.to_f64().unwrap(), p.to_f64().unwrap());
+                let denominator = t.abs() + p.abs();
+                if denominator > 0.0 {
+                    errors.push(2.0 * (t - p).abs() / denominator);
+                } else {
+                    errors.push(0.0);
+                }
+            }
+            Ok(weighted_mean(&errors, &weights))
+        }
+    }
+}
+
+pub fn symmetric_mean_absolute_percentage_error_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        symmetric_mean_absolute_percentage_error,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![3.0, -0.5, 2.0, 7.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![2.5, 0.0, 2.0, 8.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array_missing() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[rstest]
+    fn test_equivalent(target_named_array: NamedArray<f64>) {
+        assert_eq!(
+            mean_absolute_percentage_error(
+                target_named_array.clone(),
+                target_named_array.clone(),
+                None
+            )
+            .unwrap(),
+            0.0
+        );
+        assert_eq!(
+            symmetric_mean_absolute_percentage_error(
+                target_named_array.clone(),
+                target_named_array,
+                None
+            )
+            .unwrap(),
+            0.0
+        );
+    }
+
+    #[rstest]
+    fn test_mismatch_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array_missing: NamedArray<f64>,
+    ) {
+        assert!(mean_absolute_percentage_error(
+            target_named_array.clone(),
+            predictions_named_array_missing.clone(),
+            None
+        )
+        .is_err());
+        assert!(symmetric_mean_absolute_percentage_error(
+            target_named_array,
+            predictions_named_array_missing,
+            None
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_mape(target_named_array: NamedArray<f64>, predictions_named_array: NamedArray<f64>) {
+        assert_float_relative_eq!(
+            mean_absolute_percentage_error(target_named_array, predictions_named_array, None)
+                .unwrap(),
+            0.327381,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_smape(target_named_array: NamedArray<f64>, predictions_named_array: NamedArray<f64>) {
+        assert_float_relative_eq!(
+            symmetric_mean_absolute_percentage_error(
+                target_named_array,
+                predictions_named_array,
+                None
+            )
+            .unwrap(),
+            0.578788,
+            0.001
+        );
+    }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0, 1.0, 1.0, 4.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            mean_absolute_percentage_error(
+                target_named_array.clone(),
+                predictions_named_array.clone(),
+                sample_weight.clone()
+            )
+            .unwrap(),
+            0.248299,
+            0.001
+        );
+        assert_float_relative_eq!(
+            symmetric_mean_absolute_percentage_error(
+                target_named_array,
+                predictions_named_array,
+                sample_weight
+            )
+            .unwrap(),
+            0.387879,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = mean_absolute_percentage_error_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 0.327381, 0.001);
+        assert_eq!(raw[1], 0.0);
+        let raw = symmetric_mean_absolute_percentage_error_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 0.578788, 0.001);
+        assert_eq!(raw[1], 0.0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::core::base::checks;
+use crate::core::weights::{score_outputs, weight_checks, weighted_mean, MultiOutput};
 use num::{Num, ToPrimitive};
 use rustlearn_array::namedarray::NamedArray;
 use rustlearn_errors::RustLearnError;
@@ -6,6 +7,7 @@ use rustlearn_errors::RustLearnError;
 pub fn mean_squared_error<T>(
     y_true: NamedArray<T>,
     y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
 ) -> Result<f64, RustLearnError>
 where
     T: Num,
@@ -16,13 +18,14 @@ where
     match checked {
         Some(checked) => Err(checked),
         None => {
-            let mut total: f64 = 0.0;
+            let weights = weight_checks(&sample_weight, y_true.len())?;
             let vec_true: Vec<T> = y_true.data;
             let vec_pred: Vec<T> = y_pred.data;
+            let mut squares: Vec<f64> = Vec::new();
             for (i, val) in vec_true.iter().enumerate() {
-                total += (val.to_f64().unwrap() - vec_pred[i].to_f64().unwrap()).powi(2);
+                squares.push((val.to_f64().unwrap() - vec_pred[i].to_f64().unwrap()).powi(2));
             }
-            Ok(total / vec_true.len() as f64)
+            Ok(weighted_mean(&squares, &weights))
         }
     }
 }
@@ -30,23 +33,65 @@ where
 pub fn root_mean_squared_error<T>(
     y_true: NamedArray<T>,
     y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
 ) -> Result<f64, RustLearnError>
 where
     T: Num,
     T: ToPrimitive,
     T: Copy,
 {
-    let mse = mean_squared_error(y_true, y_pred);
+    let mse = mean_squared_error(y_true, y_pred, sample_weight);
     match mse {
         Ok(mse) => Ok(mse.powf(0.5)),
         Err(e) => Err(e),
     }
 }
 
+pub fn mean_squared_error_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        mean_squared_error,
+    )
+}
+
+pub fn root_mean_squared_error_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        root_mean_squared_error,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use assert_float_eq::assert_float_relative_eq;
     use rstest::*;
 
     #[fixture]
@@ -76,7 +121,8 @@ mod tests {
     #[rstest]
     fn test_equivalent(target_named_array: NamedArray<f64>) {
         assert_eq!(
-            mean_squared_error(target_named_array.clone(), target_named_array.clone()).unwrap(),
+            mean_squared_error(target_named_array.clone(), target_named_array.clone(), None)
+                .unwrap(),
             0.0
         );
     }
@@ -86,13 +132,15 @@ mod tests {
         target_named_array: NamedArray<f64>,
         predictions_named_array_missing: NamedArray<f64>,
     ) {
-        assert!(mean_squared_error(target_named_array, predictions_named_array_missing).is_err())
+        assert!(
+            mean_squared_error(target_named_array, predictions_named_array_missing, None).is_err()
+        )
     }
 
     #[rstest]
     fn test_mse(target_named_array: NamedArray<f64>, predictions_named_array: NamedArray<f64>) {
         assert_eq!(
-            mean_squared_error(target_named_array, predictions_named_array).unwrap(),
+            mean_squared_error(target_named_array, predictions_named_array, None).unwrap(),
             20915.4
         );
     }
@@ -100,7 +148,7 @@ mod tests {
     #[rstest]
     fn test_equivalent_rmse(target_named_array: NamedArray<f64>) {
         assert_eq!(
-            root_mean_squared_error(target_named_array.clone(), target_named_array.clone())
+            root_mean_squared_error(target_named_array.clone(), target_named_array.clone(), None)
                 .unwrap(),
             0.0
         );
@@ -112,15 +160,73 @@ mod tests {
         predictions_named_array_missing: NamedArray<f64>,
     ) {
         assert!(
-            root_mean_squared_error(target_named_array, predictions_named_array_missing).is_err()
+            root_mean_squared_error(target_named_array, predictions_named_array_missing, None)
+                .is_err()
         )
     }
 
     #[rstest]
     fn test_rmse(target_named_array: NamedArray<f64>, predictions_named_array: NamedArray<f64>) {
         assert_eq!(
-            root_mean_squared_error(target_named_array, predictions_named_array).unwrap(),
+            root_mean_squared_error(target_named_array, predictions_named_array, None).unwrap(),
             144.62157515391678
         );
     }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![2.0, 1.0, 1.0, 1.0, 1.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            mean_squared_error(
+                target_named_array.clone(),
+                predictions_named_array.clone(),
+                sample_weight.clone()
+            )
+            .unwrap(),
+            21179.5,
+            0.001
+        );
+        assert_float_relative_eq!(
+            root_mean_squared_error(target_named_array, predictions_named_array, sample_weight)
+                .unwrap(),
+            21179.5_f64.sqrt(),
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = mean_squared_error_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 20915.4, 0.001);
+        assert_eq!(raw[1], 0.0);
+        let raw = root_mean_squared_error_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 144.62157515391678, 0.001);
+        assert_eq!(raw[1], 0.0);
+    }
 }
@@ -0,0 +1,162 @@
+use crate::core::base::checks;
+use crate::core::weights::{score_outputs, weight_checks, weighted_mean, MultiOutput};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub fn mean_squared_log_error<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let weights = weight_checks(&sample_weight, y_true.len())?;
+            let mut errors: Vec<f64> = Vec::new();
+            for (t, p) in y_true.data.iter().zip(y_pred.data.iter()) {
+                let (t, p) = (t.to_f64().unwrap(), p.to_f64().unwrap());
+                if t < 0.0 || p < 0.0 {
+                    return Err(RustLearnError::ValidationError(ErrString::from(
+                        "mean squared log error needs non-negative values",
+                    )));
+                }
+                errors.push((t.ln_1p() - p.ln_1p()).powi(2));
+            }
+            Ok(weighted_mean(&errors, &weights))
+        }
+    }
+}
+
+pub fn mean_squared_log_error_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        mean_squared_log_error,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![3.0, 5.0, 2.5, 7.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![2.5, 5.0, 4.0, 8.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array_missing() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[rstest]
+    fn test_equivalent(target_named_array: NamedArray<f64>) {
+        assert_eq!(
+            mean_squared_log_error(target_named_array.clone(), target_named_array.clone(), None)
+                .unwrap(),
+            0.0
+        );
+    }
+
+    #[rstest]
+    fn test_mismatch_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array_missing: NamedArray<f64>,
+    ) {
+        assert!(
+            mean_squared_log_error(target_named_array, predictions_named_array_missing, None)
+                .is_err()
+        );
+    }
+
+    #[rstest]
+    fn test_msle(target_named_array: NamedArray<f64>, predictions_named_array: NamedArray<f64>) {
+        assert_float_relative_eq!(
+            mean_squared_log_error(target_named_array, predictions_named_array, None).unwrap(),
+            0.039730,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_negative_error(target_named_array: NamedArray<f64>) {
+        let negative = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![2.5, -5.0, 4.0, 8.0],
+        };
+        assert!(mean_squared_log_error(target_named_array, negative, None).is_err());
+    }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0, 1.0, 1.0, 4.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            mean_squared_log_error(target_named_array, predictions_named_array, sample_weight)
+                .unwrap(),
+            0.028648,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = mean_squared_log_error_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 0.039730, 0.001);
+        assert_eq!(raw[1], 0.0);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::core::base::checks;
+use crate::core::weights::{score_outputs, weight_checks, weighted_percentile, MultiOutput};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+// unweighted this is the usual interpolated median; with weights it is the
+// weighted 50th percentile, which is always one of the errors
+pub fn median_absolute_error<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let errors: Vec<f64> = y_true
+                .data
+                .iter()
+                .zip(y_pred.data.iter())
+                .map(|(t, p)| (t.to_f64().unwrap() - p.to_f64().unwrap()).abs())
+                .collect();
+            match sample_weight {
+                None => NamedArray {
+                    name: "absolute_error".to_string(),
+                    data: errors,
+                }
+                .median(),
+                Some(_) => {
+                    let weights = weight_checks(&sample_weight, errors.len())?;
+                    Ok(weighted_percentile(&errors, &weights, 0.5))
+                }
+            }
+        }
+    }
+}
+
+pub fn median_absolute_error_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(
+        y_true,
+        y_pred,
+        sample_weight,
+        multioutput,
+        median_absolute_error,
+    )
+}
+
+// takes no sample weights: weighting rows cannot change which error is largest
+pub fn max_error<T>(y_true: NamedArray<T>, y_pred: NamedArray<T>) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let checked = checks(y_true.clone(), y_pred.clone());
+    match checked {
+        Some(checked) => Err(checked),
+        None => {
+            let errors: Vec<f64> = y_true
+                .data
+                .iter()
+                .zip(y_pred.data.iter())
+                .map(|(t, p)| (t.to_f64().unwrap() - p.to_f64().unwrap()).abs())
+                .collect();
+            NamedArray {
+                name: "absolute_error".to_string(),
+                data: errors,
+            }
+            .max()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![3.0, -0.5, 2.0, 7.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![2.5, 0.0, 2.0, 8.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array_missing() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[rstest]
+    fn test_equivalent(target_named_array: NamedArray<f64>) {
+        assert_eq!(
+            median_absolute_error(target_named_array.clone(), target_named_array.clone(), None)
+                .unwrap(),
+            0.0
+        );
+        assert_eq!(
+            max_error(target_named_array.clone(), target_named_array).unwrap(),
+            0.0
+        );
+    }
+
+    #[rstest]
+    fn test_mismatch_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array_missing: NamedArray<f64>,
+    ) {
+        assert!(median_absolute_error(
+            target_named_array.clone(),
+            predictions_named_array_missing.clone(),
+            None
+        )
+        .is_err());
+        assert!(max_error(target_named_array, predictions_named_array_missing).is_err());
+    }
+
+    #[rstest]
+    fn test_median_absolute_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        assert_eq!(
+            median_absolute_error(target_named_array, predictions_named_array, None).unwrap(),
+            0.5
+        );
+    }
+
+    #[rstest]
+    fn test_max_error(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        assert_eq!(
+            max_error(target_named_array, predictions_named_array).unwrap(),
+            1.0
+        );
+    }
+
+    #[rstest]
+    fn test_empty() {
+        let empty: NamedArray<f64> = NamedArray {
+            name: "empty".to_string(),
+            data: vec![],
+        };
+        assert!(median_absolute_error(empty.clone(), empty.clone(), None).is_err());
+        assert!(max_error(empty.clone(), empty).is_err());
+    }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![1.0, 1.0, 1.0, 4.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            median_absolute_error(target_named_array, predictions_named_array, sample_weight)
+                .unwrap(),
+            1.0,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = median_absolute_error_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 0.5, 0.001);
+        assert_eq!(raw[1], 0.0);
+    }
+}
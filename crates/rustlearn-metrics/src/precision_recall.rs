@@ -0,0 +1,226 @@
+use crate::core::classification::{
+    average_score, class_counts, classification_checks, safe_divide, Average,
+};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub fn precision_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    average: Average,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    average_score(&class_counts(&y_true, &y_pred), average, |tp, fp, _| {
+        safe_divide(tp, tp + fp)
+    })
+}
+
+pub fn recall_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    average: Average,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    average_score(&class_counts(&y_true, &y_pred), average, |tp, _, fn_| {
+        safe_divide(tp, tp + fn_)
+    })
+}
+
+// beta weights recall beta times as much as precision
+pub fn fbeta_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    beta: f64,
+    average: Average,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    if beta <= 0.0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "beta must be positive",
+        )));
+    }
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    let beta2 = beta * beta;
+    average_score(&class_counts(&y_true, &y_pred), average, |tp, fp, fn_| {
+        safe_divide((1.0 + beta2) * tp, (1.0 + beta2) * tp + beta2 * fn_ + fp)
+    })
+}
+
+pub fn f1_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    average: Average,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    fbeta_score(y_true, y_pred, 1.0, average)
+}
+
+pub fn jaccard_score<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    average: Average,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_pred) = classification_checks(&y_true, &y_pred)?;
+    average_score(&class_counts(&y_true, &y_pred), average, |tp, fp, fn_| {
+        safe_divide(tp, tp + fp + fn_)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 2, 0, 1, 2],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 2, 1, 0, 0, 1],
+        }
+    }
+
+    #[fixture]
+    fn binary_target() -> NamedArray<i32> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 1, 0, 1, 1],
+        }
+    }
+
+    #[fixture]
+    fn binary_predictions() -> NamedArray<i32> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 1, 0, 0, 1, 1],
+        }
+    }
+
+    #[rstest]
+    fn test_binary(binary_target: NamedArray<i32>, binary_predictions: NamedArray<i32>) {
+        let (t, p) = (binary_target, binary_predictions);
+        assert_eq!(
+            precision_score(t.clone(), p.clone(), Average::Binary).unwrap(),
+            1.0
+        );
+        assert_eq!(
+            recall_score(t.clone(), p.clone(), Average::Binary).unwrap(),
+            0.75
+        );
+        assert_float_relative_eq!(
+            f1_score(t.clone(), p.clone(), Average::Binary).unwrap(),
+            6.0 / 7.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            fbeta_score(t.clone(), p.clone(), 0.5, Average::Binary).unwrap(),
+            0.9375,
+            0.001
+        );
+        assert_eq!(jaccard_score(t, p, Average::Binary).unwrap(), 0.75);
+    }
+
+    #[rstest]
+    fn test_multiclass(
+        target_named_array: NamedArray<i32>,
+        predictions_named_array: NamedArray<i32>,
+    ) {
+        let (t, p) = (target_named_array, predictions_named_array);
+        let cases = [
+            (Average::Macro, 2.0 / 9.0, 1.0 / 3.0, 4.0 / 15.0, 2.0 / 9.0),
+            (Average::Micro, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0, 0.2),
+            (
+                Average::Weighted,
+                2.0 / 9.0,
+                1.0 / 3.0,
+                4.0 / 15.0,
+                2.0 / 9.0,
+            ),
+        ];
+        for (average, precision, recall, f1, jaccard) in cases {
+            assert_float_relative_eq!(
+                precision_score(t.clone(), p.clone(), average).unwrap(),
+                precision,
+                0.001
+            );
+            assert_float_relative_eq!(
+                recall_score(t.clone(), p.clone(), average).unwrap(),
+                recall,
+                0.001
+            );
+            assert_float_relative_eq!(f1_score(t.clone(), p.clone(), average).unwrap(), f1, 0.001);
+            assert_float_relative_eq!(
+                jaccard_score(t.clone(), p.clone(), average).unwrap(),
+                jaccard,
+                0.001
+            );
+        }
+        assert!(precision_score(t, p, Average::Binary).is_err());
+    }
+
+    #[rstest]
+    fn test_weighted_uses_support() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 0, 0, 1],
+        };
+        let y_pred = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0, 0, 0, 0],
+        };
+        // class 0 recall 1 with support 3, class 1 recall 0 with support 1
+        assert_eq!(
+            recall_score(y_true.clone(), y_pred.clone(), Average::Weighted).unwrap(),
+            0.75
+        );
+        assert_eq!(recall_score(y_true, y_pred, Average::Macro).unwrap(), 0.5);
+    }
+
+    #[rstest]
+    fn test_errors(binary_target: NamedArray<i32>, binary_predictions: NamedArray<i32>) {
+        assert!(fbeta_score(
+            binary_target.clone(),
+            binary_predictions.clone(),
+            0.0,
+            Average::Binary
+        )
+        .is_err());
+        let short = NamedArray {
+            name: "predictions".to_string(),
+            data: vec![0],
+        };
+        assert!(f1_score(binary_target, short, Average::Macro).is_err());
+    }
+}
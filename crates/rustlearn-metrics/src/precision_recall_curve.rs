@@ -0,0 +1,147 @@
+use crate::core::thresholds::{score_checks, threshold_counts};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+// thresholds increase; precision and recall carry one extra final point
+// (1, 0) with no threshold, so the curve ends on the precision axis
+#[derive(Debug, PartialEq, Clone)]
+pub struct PrecisionRecallCurve {
+    pub precision: Vec<f64>,
+    pub recall: Vec<f64>,
+    pub thresholds: Vec<f64>,
+}
+
+pub fn precision_recall_curve<T>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+) -> Result<PrecisionRecallCurve, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_score) = score_checks(&y_true, &y_score)?;
+    let counts = threshold_counts(&y_true, &y_score)?;
+    let positives = counts.positives();
+    if positives == 0.0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "precision recall curve needs at least one positive label",
+        )));
+    }
+
+    let mut curve = PrecisionRecallCurve {
+        precision: Vec::new(),
+        recall: Vec::new(),
+        thresholds: Vec::new(),
+    };
+    for i in (0..counts.thresholds.len()).rev() {
+        let (tp, fp) = (counts.true_positives[i], counts.false_positives[i]);
+        curve.precision.push(tp / (tp + fp));
+        curve.recall.push(tp / positives);
+        curve.thresholds.push(counts.thresholds[i]);
+    }
+    curve.precision.push(1.0);
+    curve.recall.push(0.0);
+    Ok(curve)
+}
+
+// the step-wise sum of precision weighted by each increase in recall, with
+// no interpolation between thresholds
+pub fn average_precision_score<T>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let curve = precision_recall_curve(y_true, y_score)?;
+    Ok((0..curve.thresholds.len())
+        .map(|i| (curve.recall[i] - curve.recall[i + 1]) * curve.precision[i])
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 0.0, 1.0, 1.0],
+        }
+    }
+
+    #[fixture]
+    fn scores_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "scores".to_string(),
+            data: vec![0.1, 0.4, 0.35, 0.8],
+        }
+    }
+
+    #[rstest]
+    fn test_precision_recall_curve(
+        target_named_array: NamedArray<f64>,
+        scores_named_array: NamedArray<f64>,
+    ) {
+        let curve = precision_recall_curve(target_named_array, scores_named_array).unwrap();
+        assert_eq!(curve.thresholds, vec![0.1, 0.35, 0.4, 0.8]);
+        assert_eq!(curve.recall, vec![1.0, 1.0, 0.5, 0.5, 0.0]);
+        assert_float_relative_eq!(curve.precision[1], 2.0 / 3.0, 0.001);
+        assert_eq!(curve.precision[2..], [0.5, 1.0, 1.0]);
+    }
+
+    #[rstest]
+    fn test_average_precision(
+        target_named_array: NamedArray<f64>,
+        scores_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            average_precision_score(target_named_array.clone(), scores_named_array).unwrap(),
+            5.0 / 6.0,
+            0.001
+        );
+        // every row tied: a single threshold at the base rate
+        let tied = NamedArray {
+            name: "scores".to_string(),
+            data: vec![0.3; 4],
+        };
+        assert_eq!(
+            average_precision_score(target_named_array, tied).unwrap(),
+            0.5
+        );
+    }
+
+    #[rstest]
+    fn test_integer_labels(scores_named_array: NamedArray<f64>) {
+        let target = NamedArray {
+            name: "target".to_string(),
+            data: vec![0_u8, 0, 1, 1],
+        };
+        assert_float_relative_eq!(
+            average_precision_score(target, scores_named_array).unwrap(),
+            5.0 / 6.0,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_errors(scores_named_array: NamedArray<f64>) {
+        let no_positives = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0; 4],
+        };
+        assert!(average_precision_score(no_positives, scores_named_array.clone()).is_err());
+        let short = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0],
+        };
+        assert!(precision_recall_curve(short, scores_named_array).is_err());
+    }
+}
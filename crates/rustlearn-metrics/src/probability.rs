@@ -0,0 +1,254 @@
+use crate::core::classification::{finite_label_checks, labels_f64, unique_labels};
+use crate::core::thresholds::{binary_checks, score_checks};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub fn probability_checks(y_prob: &[f64]) -> Result<(), RustLearnError> {
+    if y_prob.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "probabilities must be between 0 and 1",
+        )));
+    }
+    Ok(())
+}
+
+fn eps_checks(eps: f64) -> Result<(), RustLearnError> {
+    if eps <= 0.0 || eps >= 0.5 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "eps must be between 0 and 0.5",
+        )));
+    }
+    Ok(())
+}
+
+// y_prob is the probability of label 1; probabilities are clipped to
+// [eps, 1 - eps] so a confident mistake costs a large but finite amount
+pub fn log_loss<T>(
+    y_true: NamedArray<T>,
+    y_prob: NamedArray<f64>,
+    eps: f64,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    eps_checks(eps)?;
+    let (y_true, y_prob) = score_checks(&y_true, &y_prob)?;
+    binary_checks(&y_true, &y_prob)?;
+    probability_checks(&y_prob)?;
+    let total: f64 = y_true
+        .iter()
+        .zip(y_prob.iter())
+        .map(|(y, p)| {
+            let p = p.clamp(eps, 1.0 - eps);
+            -(y * p.ln() + (1.0 - y) * (1.0 - p).ln())
+        })
+        .sum();
+    Ok(total / y_true.len() as f64)
+}
+
+// y_prob holds one column per class, in the sorted order of the labels in
+// y_true; rows are renormalised after clipping
+pub fn log_loss_multiclass<T>(
+    y_true: NamedArray<T>,
+    y_prob: Vec<NamedArray<f64>>,
+    eps: f64,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    eps_checks(eps)?;
+    let mut probs: Vec<Vec<f64>> = Vec::new();
+    for column in y_prob.iter() {
+        let (_, prob) = score_checks(&y_true, column)?;
+        probability_checks(&prob)?;
+        probs.push(prob);
+    }
+    let y_true = labels_f64(&y_true);
+    finite_label_checks(&y_true)?;
+    let classes = unique_labels(&y_true, &[]);
+    if probs.len() != classes.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(format!(
+            "expected one probability column per class ({}), got {}",
+            classes.len(),
+            probs.len()
+        ))));
+    }
+
+    let mut total = 0.0;
+    for (row, y) in y_true.iter().enumerate() {
+        let clipped: Vec<f64> = probs.iter().map(|p| p[row].clamp(eps, 1.0 - eps)).collect();
+        let k = classes.binary_search_by(|c| c.total_cmp(y)).unwrap();
+        total -= (clipped[k] / clipped.iter().sum::<f64>()).ln();
+    }
+    Ok(total / y_true.len() as f64)
+}
+
+// mean squared difference between the probability of label 1 and the label
+pub fn brier_score_loss<T>(
+    y_true: NamedArray<T>,
+    y_prob: NamedArray<f64>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_prob) = score_checks(&y_true, &y_prob)?;
+    binary_checks(&y_true, &y_prob)?;
+    probability_checks(&y_prob)?;
+    let total: f64 = y_true
+        .iter()
+        .zip(y_prob.iter())
+        .map(|(y, p)| (y - p).powi(2))
+        .sum();
+    Ok(total / y_true.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0, 1.0, 0.0],
+        }
+    }
+
+    #[fixture]
+    fn probabilities_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "probabilities".to_string(),
+            data: vec![0.1, 0.9, 0.8, 0.35],
+        }
+    }
+
+    #[rstest]
+    fn test_log_loss(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            log_loss(target_named_array, probabilities_named_array, 1e-15).unwrap(),
+            0.21616,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_integer_labels(probabilities_named_array: NamedArray<f64>) {
+        let target = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 1, 1, 0],
+        };
+        assert_float_relative_eq!(
+            log_loss(target.clone(), probabilities_named_array.clone(), 1e-15).unwrap(),
+            0.21616,
+            0.001
+        );
+        assert_float_relative_eq!(
+            brier_score_loss(target, probabilities_named_array).unwrap(),
+            0.045625,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_log_loss_clipping() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0],
+        };
+        let y_prob = NamedArray {
+            name: "probabilities".to_string(),
+            data: vec![0.0],
+        };
+        assert_float_relative_eq!(
+            log_loss(y_true, y_prob, 1e-15).unwrap(),
+            -(1e-15_f64).ln(),
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_log_loss_multiclass() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0, 2.0],
+        };
+        let y_prob = vec![
+            NamedArray {
+                name: "0".to_string(),
+                data: vec![0.7, 0.2, 0.1],
+            },
+            NamedArray {
+                name: "1".to_string(),
+                data: vec![0.2, 0.6, 0.3],
+            },
+            NamedArray {
+                name: "2".to_string(),
+                data: vec![0.1, 0.2, 0.6],
+            },
+        ];
+        assert_float_relative_eq!(
+            log_loss_multiclass(y_true.clone(), y_prob.clone(), 1e-15).unwrap(),
+            -(0.7_f64.ln() + 2.0 * 0.6_f64.ln()) / 3.0,
+            0.001
+        );
+        assert!(log_loss_multiclass(y_true, y_prob[..2].to_vec(), 1e-15).is_err());
+        let nan = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, f64::NAN, 2.0],
+        };
+        assert!(log_loss_multiclass(nan, y_prob, 1e-15).is_err());
+    }
+
+    #[rstest]
+    fn test_brier_score(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            brier_score_loss(target_named_array.clone(), target_named_array.clone()).unwrap(),
+            0.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            brier_score_loss(target_named_array, probabilities_named_array).unwrap(),
+            0.045625,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_errors(
+        target_named_array: NamedArray<f64>,
+        probabilities_named_array: NamedArray<f64>,
+    ) {
+        let out_of_range = NamedArray {
+            name: "probabilities".to_string(),
+            data: vec![0.1, 1.2, 0.8, 0.35],
+        };
+        assert!(brier_score_loss(target_named_array.clone(), out_of_range.clone()).is_err());
+        assert!(log_loss(target_named_array.clone(), out_of_range, 1e-15).is_err());
+        assert!(log_loss(
+            target_named_array.clone(),
+            probabilities_named_array.clone(),
+            0.0
+        )
+        .is_err());
+        let multiclass = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0, 2.0, 0.0],
+        };
+        assert!(brier_score_loss(multiclass, probabilities_named_array).is_err());
+    }
+}
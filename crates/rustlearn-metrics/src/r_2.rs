@@ -1,36 +1,78 @@
 use crate::core::base::checks;
-use num::{pow::Pow, Num, ToPrimitive};
+use crate::core::weights::{score_outputs, weight_checks, weighted_mean, MultiOutput};
+use num::{Num, ToPrimitive};
 use rustlearn_array::namedarray::NamedArray;
-use rustlearn_errors::RustLearnError;
+use rustlearn_errors::{ErrString, RustLearnError};
 
-pub fn r_2<T>(y_true: NamedArray<T>, y_pred: NamedArray<T>) -> Result<f64, RustLearnError>
+pub fn r_2<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+) -> Result<f64, RustLearnError>
 where
     T: Num,
     T: ToPrimitive,
     T: Copy,
-    T: Into<f64>,
 {
     let checked = checks(y_true.clone(), y_pred.clone());
     match checked {
         Some(checked) => Err(checked),
         None => {
-            let y_mean = y_true.mean().unwrap();
+            if y_true.is_empty() {
+                return Err(RustLearnError::ValidationError(ErrString::from(
+                    "array is empty",
+                )));
+            }
+            let weights = weight_checks(&sample_weight, y_true.len())?;
+            let vec_true: Vec<f64> = y_true.data.iter().map(|x| x.to_f64().unwrap()).collect();
+            let y_mean = weighted_mean(&vec_true, &weights);
             let mut ss_reg: Vec<f64> = Vec::new();
-            for (i, v) in y_true.data.clone().iter().enumerate() {
-                ss_reg.push((v.to_f64().unwrap() - y_pred.data[i].to_f64().unwrap()).pow(2))
+            let mut ss_total: Vec<f64> = Vec::new();
+            for (i, v) in vec_true.iter().enumerate() {
+                ss_reg.push((v - y_pred.data[i].to_f64().unwrap()).powi(2));
+                ss_total.push((v - y_mean).powi(2));
             }
-            let ss_total: f64 = y_true
-                .data
-                .iter()
-                .map(|x| (x.to_f64().unwrap() - y_mean).pow(2))
-                .collect::<Vec<f64>>()
-                .into_iter()
-                .sum();
-            Ok(1.0 - (ss_reg.into_iter().sum::<f64>() / ss_total))
+            Ok(1.0 - (weighted_mean(&ss_reg, &weights) / weighted_mean(&ss_total, &weights)))
         }
     }
 }
 
+pub fn r_2_multioutput<T>(
+    y_true: Vec<NamedArray<T>>,
+    y_pred: Vec<NamedArray<T>>,
+    sample_weight: Option<NamedArray<f64>>,
+    multioutput: MultiOutput,
+) -> Result<Vec<f64>, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    score_outputs(y_true, y_pred, sample_weight, multioutput, r_2)
+}
+
+// penalises r_2 for the number of predictors used to fit the model
+pub fn adjusted_r_2<T>(
+    y_true: NamedArray<T>,
+    y_pred: NamedArray<T>,
+    sample_weight: Option<NamedArray<f64>>,
+    n_predictors: usize,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let n_rows = y_true.len();
+    if n_rows <= n_predictors + 1 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "adjusted r2 needs more rows than predictors plus one",
+        )));
+    }
+    let r_2 = r_2(y_true, y_pred, sample_weight)?;
+    Ok(1.0 - (1.0 - r_2) * (n_rows - 1) as f64 / (n_rows - n_predictors - 1) as f64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,7 +106,7 @@ mod tests {
     #[rstest]
     fn test_equivalent(target_named_array: NamedArray<f64>) {
         assert_eq!(
-            r_2(target_named_array.clone(), target_named_array.clone()).unwrap(),
+            r_2(target_named_array.clone(), target_named_array.clone(), None).unwrap(),
             1.0
         );
     }
@@ -74,15 +116,71 @@ mod tests {
         target_named_array: NamedArray<f64>,
         predictions_named_array_missing: NamedArray<f64>,
     ) {
-        assert!(r_2(target_named_array, predictions_named_array_missing).is_err());
+        assert!(r_2(target_named_array, predictions_named_array_missing, None).is_err());
     }
 
     #[rstest]
     fn test_r_2(target_named_array: NamedArray<f64>, predictions_named_array: NamedArray<f64>) {
         assert_float_relative_eq!(
-            r_2(target_named_array, predictions_named_array).unwrap(),
+            r_2(target_named_array, predictions_named_array, None).unwrap(),
             0.7861208004406095,
             0.001
         );
     }
+
+    #[rstest]
+    fn test_adjusted_r_2(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        assert_float_relative_eq!(
+            adjusted_r_2(
+                target_named_array.clone(),
+                predictions_named_array.clone(),
+                None,
+                1
+            )
+            .unwrap(),
+            1.0 - (1.0 - 0.7861208004406095) * 4.0 / 3.0,
+            0.001
+        );
+        assert!(adjusted_r_2(target_named_array, predictions_named_array, None, 4).is_err());
+    }
+
+    #[fixture]
+    fn sample_weight() -> Option<NamedArray<f64>> {
+        Some(NamedArray {
+            name: "weights".to_string(),
+            data: vec![2.0, 1.0, 1.0, 1.0, 1.0],
+        })
+    }
+
+    #[rstest]
+    fn test_sample_weight(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+        sample_weight: Option<NamedArray<f64>>,
+    ) {
+        assert_float_relative_eq!(
+            r_2(target_named_array, predictions_named_array, sample_weight).unwrap(),
+            0.774145,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_multioutput(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let raw = r_2_multioutput(
+            vec![target_named_array.clone(), target_named_array.clone()],
+            vec![predictions_named_array.clone(), target_named_array.clone()],
+            None,
+            MultiOutput::RawValues,
+        )
+        .unwrap();
+        assert_float_relative_eq!(raw[0], 0.7861208004406095, 0.001);
+        assert_eq!(raw[1], 1.0);
+    }
 }
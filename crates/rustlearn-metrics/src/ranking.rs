@@ -0,0 +1,517 @@
+use crate::core::classification::labels_f64;
+use crate::core::thresholds::score_checks;
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+// relevance of each query group split into blocks of tied scores, ordered by
+// descending score; the metrics treat the rows of a block as interchangeable
+// so the result does not depend on the input order
+fn ranked_groups<T, G>(
+    y_true: &NamedArray<T>,
+    y_score: &NamedArray<f64>,
+    group: &NamedArray<G>,
+) -> Result<Vec<Vec<Vec<f64>>>, RustLearnError>
+where
+    T: Num + ToPrimitive + Copy,
+    G: ToPrimitive,
+{
+    let (relevance, scores) = score_checks(y_true, y_score)?;
+    if group.data.len() != relevance.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "group ids are not the same length as the series",
+        )));
+    }
+    let groups = labels_f64(group);
+    if relevance.iter().any(|r| *r < 0.0 || r.is_nan()) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "relevance must be non-negative",
+        )));
+    }
+    if scores.iter().any(|s| s.is_nan()) {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "scores contain NaN",
+        )));
+    }
+
+    // one sort by group and then descending score brings both the groups and
+    // the tied blocks together
+    let mut rows: Vec<usize> = (0..relevance.len()).collect();
+    rows.sort_by(|a, b| {
+        groups[*a]
+            .total_cmp(&groups[*b])
+            .then(scores[*b].total_cmp(&scores[*a]))
+    });
+    let mut ranked: Vec<Vec<Vec<f64>>> = Vec::new();
+    let mut previous: Option<usize> = None;
+    for row in rows {
+        match previous {
+            Some(p) if groups[p] == groups[row] => {
+                let blocks = ranked.last_mut().unwrap();
+                if scores[p] == scores[row] {
+                    blocks.last_mut().unwrap().push(relevance[row]);
+                } else {
+                    blocks.push(vec![relevance[row]]);
+                }
+            }
+            _ => ranked.push(vec![vec![relevance[row]]]),
+        }
+        previous = Some(row);
+    }
+    Ok(ranked)
+}
+
+fn k_checks(k: usize) -> Result<(), RustLearnError> {
+    if k == 0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "k must be at least 1",
+        )));
+    }
+    Ok(())
+}
+
+fn n_rows(blocks: &[Vec<f64>]) -> usize {
+    blocks.iter().map(|b| b.len()).sum()
+}
+
+fn cutoff(k: Option<usize>, n: usize) -> Result<usize, RustLearnError> {
+    match k {
+        Some(k) => {
+            k_checks(k)?;
+            Ok(k.min(n))
+        }
+        None => Ok(n),
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn n_relevant(block: &[f64]) -> f64 {
+    block.iter().filter(|r| **r > 0.0).count() as f64
+}
+
+// each block contributes its mean gain at every position it covers, as
+// scikit-learn's tie-averaged dcg does
+fn dcg(blocks: &[Vec<f64>], k: usize) -> f64 {
+    let mut total = 0.0;
+    let mut start = 0;
+    for block in blocks.iter() {
+        let gain = mean(block);
+        for rank in start..(start + block.len()).min(k) {
+            total += gain / ((rank + 2) as f64).log2();
+        }
+        start += block.len();
+    }
+    total
+}
+
+// the expected number of relevant rows in the first k when tied rows are
+// ordered at random
+fn hits_at_k(blocks: &[Vec<f64>], k: usize) -> f64 {
+    let mut hits = 0.0;
+    let mut start = 0;
+    for block in blocks.iter() {
+        if start >= k {
+            break;
+        }
+        let covered = (k - start).min(block.len());
+        hits += n_relevant(block) * covered as f64 / block.len() as f64;
+        start += block.len();
+    }
+    hits
+}
+
+// discounted cumulative gain with linear gains, averaged over query groups
+pub fn dcg_score<T, G>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+    group: NamedArray<G>,
+    k: Option<usize>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+    G: ToPrimitive,
+{
+    let mut scores: Vec<f64> = Vec::new();
+    for blocks in ranked_groups(&y_true, &y_score, &group)? {
+        scores.push(dcg(&blocks, cutoff(k, n_rows(&blocks))?));
+    }
+    Ok(mean(&scores))
+}
+
+// a group with no relevant rows scores 0
+pub fn ndcg_score<T, G>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+    group: NamedArray<G>,
+    k: Option<usize>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+    G: ToPrimitive,
+{
+    let mut scores: Vec<f64> = Vec::new();
+    for blocks in ranked_groups(&y_true, &y_score, &group)? {
+        let k = cutoff(k, n_rows(&blocks))?;
+        let mut ideal: Vec<f64> = blocks.iter().flatten().copied().collect();
+        ideal.sort_by(|a, b| b.total_cmp(a));
+        let ideal: Vec<Vec<f64>> = ideal.into_iter().map(|r| vec![r]).collect();
+        let ideal_dcg = dcg(&ideal, k);
+        scores.push(if ideal_dcg == 0.0 {
+            0.0
+        } else {
+            dcg(&blocks, k) / ideal_dcg
+        });
+    }
+    Ok(mean(&scores))
+}
+
+// rows with positive relevance count as relevant; average precision@k is
+// divided by min(k, relevant rows) and is 0 for a group with none. A block
+// of tied rows is a single threshold, as in average_precision_score, and a
+// block cut by k counts its share of relevant rows up to k
+pub fn mean_average_precision<T, G>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+    group: NamedArray<G>,
+    k: Option<usize>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+    G: ToPrimitive,
+{
+    let mut scores: Vec<f64> = Vec::new();
+    for blocks in ranked_groups(&y_true, &y_score, &group)? {
+        let k = cutoff(k, n_rows(&blocks))?;
+        let relevant: f64 = blocks.iter().map(|b| n_relevant(b)).sum();
+        let mut hits = 0.0;
+        let mut total = 0.0;
+        let mut start = 0;
+        for block in blocks.iter() {
+            if start >= k {
+                break;
+            }
+            let end = (start + block.len()).min(k);
+            let block_hits = n_relevant(block) * (end - start) as f64 / block.len() as f64;
+            hits += block_hits;
+            total += block_hits * hits / end as f64;
+            start += block.len();
+        }
+        scores.push(if relevant == 0.0 {
+            0.0
+        } else {
+            total / relevant.min(k as f64)
+        });
+    }
+    Ok(mean(&scores))
+}
+
+// the expected reciprocal rank of the first relevant row when tied rows are
+// ordered at random; a group with no relevant rows contributes 0
+pub fn mean_reciprocal_rank<T, G>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+    group: NamedArray<G>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+    G: ToPrimitive,
+{
+    let mut scores: Vec<f64> = Vec::new();
+    for blocks in ranked_groups(&y_true, &y_score, &group)? {
+        let mut start = 0;
+        let mut score = 0.0;
+        for block in blocks.iter() {
+            let (size, hits) = (block.len() as f64, n_relevant(block));
+            if hits > 0.0 {
+                // the chance that the first relevant row sits at each offset
+                let mut chance = hits / size;
+                for offset in 0..=(size - hits) as usize {
+                    score += chance / (start + offset + 1) as f64;
+                    let offset = offset as f64;
+                    chance *= (size - hits - offset) / (size - 1.0 - offset);
+                }
+                break;
+            }
+            start += block.len();
+        }
+        scores.push(score);
+    }
+    Ok(mean(&scores))
+}
+
+// always divides by k, so groups shorter than k cannot reach 1
+pub fn precision_at_k<T, G>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+    group: NamedArray<G>,
+    k: usize,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+    G: ToPrimitive,
+{
+    k_checks(k)?;
+    let scores: Vec<f64> = ranked_groups(&y_true, &y_score, &group)?
+        .iter()
+        .map(|blocks| hits_at_k(blocks, k) / k as f64)
+        .collect();
+    Ok(mean(&scores))
+}
+
+// a group with no relevant rows contributes 0
+pub fn recall_at_k<T, G>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+    group: NamedArray<G>,
+    k: usize,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+    G: ToPrimitive,
+{
+    k_checks(k)?;
+    let scores: Vec<f64> = ranked_groups(&y_true, &y_score, &group)?
+        .iter()
+        .map(|blocks| {
+            let relevant: f64 = blocks.iter().map(|b| n_relevant(b)).sum();
+            if relevant == 0.0 {
+                0.0
+            } else {
+                hits_at_k(blocks, k) / relevant
+            }
+        })
+        .collect();
+    Ok(mean(&scores))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn relevance() -> NamedArray<f64> {
+        NamedArray {
+            name: "relevance".to_string(),
+            data: vec![3.0, 2.0, 0.0, 0.0, 1.0, 0.0],
+        }
+    }
+
+    #[fixture]
+    fn score() -> NamedArray<f64> {
+        NamedArray {
+            name: "score".to_string(),
+            data: vec![0.9, 0.1, 0.5, 0.8, 0.7, 0.2],
+        }
+    }
+
+    #[fixture]
+    fn query() -> NamedArray<i32> {
+        NamedArray {
+            name: "query".to_string(),
+            data: vec![7, 7, 7, 2, 2, 2],
+        }
+    }
+
+    #[rstest]
+    fn test_dcg(relevance: NamedArray<f64>, score: NamedArray<f64>, query: NamedArray<i32>) {
+        // 3 / log2(2) + 2 / log2(4) and 1 / log2(3)
+        assert_float_relative_eq!(
+            dcg_score(relevance, score, query, None).unwrap(),
+            (4.0 + 1.0 / 3.0_f64.log2()) / 2.0,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_ndcg(relevance: NamedArray<f64>, score: NamedArray<f64>, query: NamedArray<i32>) {
+        assert_float_relative_eq!(
+            ndcg_score(relevance.clone(), score.clone(), query.clone(), None).unwrap(),
+            (4.0 / (3.0 + 2.0 / 3.0_f64.log2()) + 1.0 / 3.0_f64.log2()) / 2.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            ndcg_score(relevance.clone(), score.clone(), query.clone(), Some(1)).unwrap(),
+            0.5,
+            0.001
+        );
+        // scoring by relevance itself is the ideal ranking
+        assert_float_relative_eq!(
+            ndcg_score(relevance.clone(), relevance, query, None).unwrap(),
+            1.0,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_mean_average_precision(
+        relevance: NamedArray<f64>,
+        score: NamedArray<f64>,
+        query: NamedArray<i32>,
+    ) {
+        assert_float_relative_eq!(
+            mean_average_precision(relevance.clone(), score.clone(), query.clone(), None).unwrap(),
+            (5.0 / 6.0 + 0.5) / 2.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            mean_average_precision(relevance, score, query, Some(2)).unwrap(),
+            0.5,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_mean_reciprocal_rank(
+        relevance: NamedArray<f64>,
+        score: NamedArray<f64>,
+        query: NamedArray<i32>,
+    ) {
+        assert_float_relative_eq!(
+            mean_reciprocal_rank(relevance, score, query).unwrap(),
+            0.75,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_precision_recall_at_k(
+        relevance: NamedArray<f64>,
+        score: NamedArray<f64>,
+        query: NamedArray<i32>,
+    ) {
+        assert_float_relative_eq!(
+            precision_at_k(relevance.clone(), score.clone(), query.clone(), 2).unwrap(),
+            0.5,
+            0.001
+        );
+        assert_float_relative_eq!(
+            recall_at_k(relevance, score, query, 2).unwrap(),
+            0.75,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_integer_relevance(
+        relevance: NamedArray<f64>,
+        score: NamedArray<f64>,
+        query: NamedArray<i32>,
+    ) {
+        let graded = NamedArray {
+            name: "relevance".to_string(),
+            data: vec![3, 2, 0, 0, 1, 0],
+        };
+        assert_float_relative_eq!(
+            ndcg_score(graded.clone(), score.clone(), query.clone(), None).unwrap(),
+            ndcg_score(relevance.clone(), score.clone(), query.clone(), None).unwrap(),
+            0.001
+        );
+        assert_float_relative_eq!(
+            mean_reciprocal_rank(graded, score.clone(), query.clone()).unwrap(),
+            mean_reciprocal_rank(relevance, score, query).unwrap(),
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_no_relevant_rows(score: NamedArray<f64>, query: NamedArray<i32>) {
+        let relevance = NamedArray {
+            name: "relevance".to_string(),
+            data: vec![0.0; 6],
+        };
+        assert_eq!(
+            ndcg_score(relevance.clone(), score.clone(), query.clone(), None).unwrap(),
+            0.0
+        );
+        assert_eq!(
+            mean_reciprocal_rank(relevance.clone(), score.clone(), query.clone()).unwrap(),
+            0.0
+        );
+        assert_eq!(recall_at_k(relevance, score, query, 3).unwrap(), 0.0);
+    }
+
+    #[rstest]
+    fn test_errors(relevance: NamedArray<f64>, score: NamedArray<f64>, query: NamedArray<i32>) {
+        assert!(precision_at_k(relevance.clone(), score.clone(), query.clone(), 0).is_err());
+        assert!(ndcg_score(relevance.clone(), score.clone(), query.clone(), Some(0)).is_err());
+        let short = NamedArray {
+            name: "query".to_string(),
+            data: vec![1, 1],
+        };
+        assert!(dcg_score(relevance.clone(), score.clone(), short, None).is_err());
+        let negative = NamedArray {
+            name: "relevance".to_string(),
+            data: vec![-1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+        };
+        assert!(mean_reciprocal_rank(negative, score, query).is_err());
+    }
+
+    #[rstest]
+    fn test_ties_ignore_row_order() {
+        let named = |name: &str, data: Vec<f64>| NamedArray {
+            name: name.to_string(),
+            data,
+        };
+        let query = NamedArray {
+            name: "query".to_string(),
+            data: vec![1; 6],
+        };
+        let relevance = named("relevance", vec![0.0, 3.0, 0.0, 1.0, 2.0, 0.0]);
+        let score = named("score", vec![0.9, 0.5, 0.5, 0.5, 0.1, 0.1]);
+        // the same rows with each tied block reversed
+        let permuted_relevance = named("relevance", vec![1.0, 0.0, 3.0, 0.0, 2.0, 0.0]);
+        let permuted_score = named("score", vec![0.5, 0.5, 0.5, 0.1, 0.1, 0.9]);
+
+        let all = |relevance: &NamedArray<f64>, score: &NamedArray<f64>| {
+            vec![
+                dcg_score(relevance.clone(), score.clone(), query.clone(), None).unwrap(),
+                ndcg_score(relevance.clone(), score.clone(), query.clone(), Some(3)).unwrap(),
+                mean_average_precision(relevance.clone(), score.clone(), query.clone(), None)
+                    .unwrap(),
+                mean_average_precision(relevance.clone(), score.clone(), query.clone(), Some(2))
+                    .unwrap(),
+                mean_reciprocal_rank(relevance.clone(), score.clone(), query.clone()).unwrap(),
+                precision_at_k(relevance.clone(), score.clone(), query.clone(), 2).unwrap(),
+                recall_at_k(relevance.clone(), score.clone(), query.clone(), 2).unwrap(),
+            ]
+        };
+        let original = all(&relevance, &score);
+        for (a, b) in original
+            .iter()
+            .zip(all(&permuted_relevance, &permuted_score))
+        {
+            assert_float_relative_eq!(*a, b, 0.001);
+        }
+
+        // the tied block of gains 3, 0 and 1 averages to 4 / 3
+        assert_float_relative_eq!(
+            original[0],
+            4.0 / 3.0 * (1.0 / 3.0_f64.log2() + 0.5 + 1.0 / 5.0_f64.log2())
+                + 1.0 / 6.0_f64.log2()
+                + 1.0 / 7.0_f64.log2(),
+            0.001
+        );
+        // two of the three tied rows are relevant, so the first relevant row is
+        // second with chance 2 / 3 and third otherwise
+        assert_float_relative_eq!(original[4], 2.0 / 3.0 / 2.0 + 1.0 / 3.0 / 3.0, 0.001);
+        // one of the three tied rows reaches the top 2, relevant with chance 2 / 3
+        assert_float_relative_eq!(original[5], 2.0 / 3.0 / 2.0, 0.001);
+    }
+}
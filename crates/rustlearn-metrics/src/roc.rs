@@ -0,0 +1,411 @@
+use crate::core::classification::{labels_f64, Average};
+use crate::core::thresholds::{score_checks, threshold_counts};
+use num::{Num, ToPrimitive};
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MultiClass {
+    // each class against all the others
+    OneVsRest,
+    // every pair of classes, averaging both directions (Hand and Till)
+    OneVsOne,
+}
+
+// the first threshold is infinite so the curve starts at (0, 0)
+#[derive(Debug, PartialEq, Clone)]
+pub struct RocCurve {
+    pub fpr: Vec<f64>,
+    pub tpr: Vec<f64>,
+    pub thresholds: Vec<f64>,
+}
+
+// thresholds increase, so the false positive rate falls and the false
+// negative rate rises along the curve
+#[derive(Debug, PartialEq, Clone)]
+pub struct DetCurve {
+    pub fpr: Vec<f64>,
+    pub fnr: Vec<f64>,
+    pub thresholds: Vec<f64>,
+}
+
+fn roc_points(y_true: &[f64], y_score: &[f64]) -> Result<RocCurve, RustLearnError> {
+    let counts = threshold_counts(y_true, y_score)?;
+    let (positives, negatives) = (counts.positives(), counts.negatives());
+    if positives == 0.0 || negatives == 0.0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "roc curve needs both positive and negative labels",
+        )));
+    }
+    let mut curve = RocCurve {
+        fpr: vec![0.0],
+        tpr: vec![0.0],
+        thresholds: vec![f64::INFINITY],
+    };
+    for (i, threshold) in counts.thresholds.iter().enumerate() {
+        curve.fpr.push(counts.false_positives[i] / negatives);
+        curve.tpr.push(counts.true_positives[i] / positives);
+        curve.thresholds.push(*threshold);
+    }
+    Ok(curve)
+}
+
+// the trapezoid rule over the curve gives tied scores half credit, the same
+// as the Mann-Whitney statistic
+fn binary_auc(y_true: &[f64], y_score: &[f64]) -> Result<f64, RustLearnError> {
+    let curve = roc_points(y_true, y_score)?;
+    Ok((1..curve.fpr.len())
+        .map(|i| (curve.fpr[i] - curve.fpr[i - 1]) * (curve.tpr[i] + curve.tpr[i - 1]) / 2.0)
+        .sum())
+}
+
+pub fn roc_curve<T>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+) -> Result<RocCurve, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_score) = score_checks(&y_true, &y_score)?;
+    roc_points(&y_true, &y_score)
+}
+
+pub fn roc_auc_score<T>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_score) = score_checks(&y_true, &y_score)?;
+    binary_auc(&y_true, &y_score)
+}
+
+// y_score holds one column per class, in the sorted order of the labels in
+// y_true
+pub fn roc_auc_score_multiclass<T>(
+    y_true: NamedArray<T>,
+    y_score: Vec<NamedArray<f64>>,
+    multi_class: MultiClass,
+    average: Average,
+) -> Result<f64, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let mut scores: Vec<Vec<f64>> = Vec::new();
+    for column in y_score.iter() {
+        scores.push(score_checks(&y_true, column)?.1);
+    }
+    let y_true = labels_f64(&y_true);
+    let mut classes = y_true.clone();
+    classes.sort_by(|a, b| a.total_cmp(b));
+    classes.dedup();
+    if classes.len() < 2 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "roc auc needs at least two classes",
+        )));
+    }
+    if scores.len() != classes.len() {
+        return Err(RustLearnError::ValidationError(ErrString::from(format!(
+            "expected one score column per class ({}), got {}",
+            classes.len(),
+            scores.len()
+        ))));
+    }
+    let one_hot = |label: f64| -> Vec<f64> {
+        y_true
+            .iter()
+            .map(|y| if *y == label { 1.0 } else { 0.0 })
+            .collect()
+    };
+    let prevalence = |label: f64| y_true.iter().filter(|y| **y == label).count() as f64;
+
+    // (auc, weight) per class or per pair of classes
+    let mut aucs: Vec<(f64, f64)> = Vec::new();
+    match (multi_class, average) {
+        (_, Average::Binary) => {
+            return Err(RustLearnError::ValidationError(ErrString::from(
+                "binary averaging is not defined for multiclass roc auc, use roc_auc_score",
+            )))
+        }
+        (MultiClass::OneVsOne, Average::Micro) => {
+            return Err(RustLearnError::ValidationError(ErrString::from(
+                "micro averaging is only defined for one-vs-rest",
+            )))
+        }
+        (MultiClass::OneVsRest, Average::Micro) => {
+            let flat_true: Vec<f64> = classes.iter().flat_map(|c| one_hot(*c)).collect();
+            let flat_score: Vec<f64> = scores.iter().flatten().copied().collect();
+            return binary_auc(&flat_true, &flat_score);
+        }
+        (MultiClass::OneVsRest, _) => {
+            for (k, class) in classes.iter().enumerate() {
+                aucs.push((
+                    binary_auc(&one_hot(*class), &scores[k])?,
+                    prevalence(*class),
+                ));
+            }
+        }
+        (MultiClass::OneVsOne, _) => {
+            for j in 0..classes.len() {
+                for k in (j + 1)..classes.len() {
+                    let rows: Vec<usize> = (0..y_true.len())
+                        .filter(|i| y_true[*i] == classes[j] || y_true[*i] == classes[k])
+                        .collect();
+                    let pair_auc = |positive: usize| {
+                        let labels: Vec<f64> = rows
+                            .iter()
+                            .map(|i| {
+                                if y_true[*i] == classes[positive] {
+                                    1.0
+                                } else {
+                                    0.0
+                                }
+                            })
+                            .collect();
+                        let score: Vec<f64> = rows.iter().map(|i| scores[positive][*i]).collect();
+                        binary_auc(&labels, &score)
+                    };
+                    aucs.push((
+                        (pair_auc(j)? + pair_auc(k)?) / 2.0,
+                        prevalence(classes[j]) + prevalence(classes[k]),
+                    ));
+                }
+            }
+        }
+    }
+
+    match average {
+        Average::Weighted => {
+            let total: f64 = aucs.iter().map(|(_, w)| w).sum();
+            Ok(aucs.iter().map(|(auc, w)| auc * w).sum::<f64>() / total)
+        }
+        _ => Ok(aucs.iter().map(|(auc, _)| auc).sum::<f64>() / aucs.len() as f64),
+    }
+}
+
+pub fn det_curve<T>(
+    y_true: NamedArray<T>,
+    y_score: NamedArray<f64>,
+) -> Result<DetCurve, RustLearnError>
+where
+    T: Num,
+    T: ToPrimitive,
+    T: Copy,
+{
+    let (y_true, y_score) = score_checks(&y_true, &y_score)?;
+    let counts = threshold_counts(&y_true, &y_score)?;
+    let (positives, negatives) = (counts.positives(), counts.negatives());
+    if positives == 0.0 || negatives == 0.0 {
+        return Err(RustLearnError::ValidationError(ErrString::from(
+            "det curve needs both positive and negative labels",
+        )));
+    }
+
+    // keep from the last threshold with no false positives up to the first
+    // with no false negatives; beyond those the curve does not move
+    let first = counts
+        .false_positives
+        .iter()
+        .take_while(|fp| **fp == counts.false_positives[0])
+        .count()
+        - 1;
+    let last = counts
+        .true_positives
+        .iter()
+        .position(|tp| *tp == positives)
+        .unwrap();
+
+    let mut curve = DetCurve {
+        fpr: Vec::new(),
+        fnr: Vec::new(),
+        thresholds: Vec::new(),
+    };
+    for i in (first..=last).rev() {
+        curve.fpr.push(counts.false_positives[i] / negatives);
+        curve
+            .fnr
+            .push((positives - counts.true_positives[i]) / positives);
+        curve.thresholds.push(counts.thresholds[i]);
+    }
+    Ok(curve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 0.0, 1.0, 1.0],
+        }
+    }
+
+    #[fixture]
+    fn scores_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "scores".to_string(),
+            data: vec![0.1, 0.4, 0.35, 0.8],
+        }
+    }
+
+    #[fixture]
+    fn multiclass_target() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0],
+        }
+    }
+
+    #[fixture]
+    fn multiclass_scores() -> Vec<NamedArray<f64>> {
+        vec![
+            NamedArray {
+                name: "0".to_string(),
+                data: vec![0.8, 0.1, 0.1, 0.6, 0.3, 0.2],
+            },
+            NamedArray {
+                name: "1".to_string(),
+                data: vec![0.1, 0.7, 0.3, 0.2, 0.25, 0.3],
+            },
+            NamedArray {
+                name: "2".to_string(),
+                data: vec![0.1, 0.2, 0.6, 0.2, 0.3, 0.5],
+            },
+        ]
+    }
+
+    #[rstest]
+    fn test_integer_labels(scores_named_array: NamedArray<f64>) {
+        let target = NamedArray {
+            name: "target".to_string(),
+            data: vec![0, 0, 1, 1],
+        };
+        assert_eq!(
+            roc_auc_score(target.clone(), scores_named_array.clone()).unwrap(),
+            0.75
+        );
+        assert_eq!(
+            det_curve(target, scores_named_array).unwrap().thresholds,
+            vec![0.35, 0.4, 0.8]
+        );
+    }
+
+    #[rstest]
+    fn test_roc_curve(target_named_array: NamedArray<f64>, scores_named_array: NamedArray<f64>) {
+        let curve = roc_curve(target_named_array.clone(), scores_named_array.clone()).unwrap();
+        assert_eq!(curve.fpr, vec![0.0, 0.0, 0.5, 0.5, 1.0]);
+        assert_eq!(curve.tpr, vec![0.0, 0.5, 0.5, 1.0, 1.0]);
+        assert_eq!(curve.thresholds, vec![f64::INFINITY, 0.8, 0.4, 0.35, 0.1]);
+        assert_eq!(
+            roc_auc_score(target_named_array, scores_named_array).unwrap(),
+            0.75
+        );
+    }
+
+    #[rstest]
+    fn test_roc_auc_ties() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0, 1.0, 0.0],
+        };
+        let tied = NamedArray {
+            name: "scores".to_string(),
+            data: vec![0.2, 0.5, 0.5, 0.5],
+        };
+        // two wins and two ties out of four pairs
+        assert_eq!(roc_auc_score(y_true.clone(), tied).unwrap(), 0.75);
+        let constant = NamedArray {
+            name: "scores".to_string(),
+            data: vec![0.5; 4],
+        };
+        assert_eq!(roc_auc_score(y_true, constant).unwrap(), 0.5);
+    }
+
+    #[rstest]
+    fn test_roc_auc_errors(scores_named_array: NamedArray<f64>) {
+        let one_class = NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0; 4],
+        };
+        assert!(roc_auc_score(one_class, scores_named_array.clone()).is_err());
+        let multiclass = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0, 2.0, 1.0],
+        };
+        assert!(roc_auc_score(multiclass, scores_named_array).is_err());
+    }
+
+    #[rstest]
+    fn test_roc_auc_multiclass(
+        multiclass_target: NamedArray<f64>,
+        multiclass_scores: Vec<NamedArray<f64>>,
+    ) {
+        let cases = [
+            (MultiClass::OneVsRest, Average::Macro, 11.0 / 12.0),
+            (MultiClass::OneVsRest, Average::Weighted, 11.0 / 12.0),
+            (MultiClass::OneVsRest, Average::Micro, 68.0 / 72.0),
+            (MultiClass::OneVsOne, Average::Macro, 11.0 / 12.0),
+            (MultiClass::OneVsOne, Average::Weighted, 11.0 / 12.0),
+        ];
+        for (multi_class, average, expected) in cases {
+            assert_float_relative_eq!(
+                roc_auc_score_multiclass(
+                    multiclass_target.clone(),
+                    multiclass_scores.clone(),
+                    multi_class,
+                    average
+                )
+                .unwrap(),
+                expected,
+                0.001
+            );
+        }
+    }
+
+    #[rstest]
+    fn test_roc_auc_multiclass_errors(
+        multiclass_target: NamedArray<f64>,
+        multiclass_scores: Vec<NamedArray<f64>>,
+    ) {
+        assert!(roc_auc_score_multiclass(
+            multiclass_target.clone(),
+            multiclass_scores[..2].to_vec(),
+            MultiClass::OneVsRest,
+            Average::Macro
+        )
+        .is_err());
+        assert!(roc_auc_score_multiclass(
+            multiclass_target.clone(),
+            multiclass_scores.clone(),
+            MultiClass::OneVsOne,
+            Average::Micro
+        )
+        .is_err());
+        assert!(roc_auc_score_multiclass(
+            multiclass_target,
+            multiclass_scores,
+            MultiClass::OneVsRest,
+            Average::Binary
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_det_curve(target_named_array: NamedArray<f64>, scores_named_array: NamedArray<f64>) {
+        let curve = det_curve(target_named_array, scores_named_array).unwrap();
+        assert_eq!(curve.fpr, vec![0.5, 0.5, 0.0]);
+        assert_eq!(curve.fnr, vec![0.0, 0.5, 0.5]);
+        assert_eq!(curve.thresholds, vec![0.35, 0.4, 0.8]);
+    }
+}
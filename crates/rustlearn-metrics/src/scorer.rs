@@ -0,0 +1,382 @@
+use crate::accuracy::{accuracy_score, balanced_accuracy_score};
+use crate::agreement::{cohen_kappa_score, matthews_corrcoef};
+use crate::clustering::external::{
+    adjusted_mutual_info_score, adjusted_rand_score, normalized_mutual_info_score, v_measure_score,
+    AverageMethod,
+};
+use crate::core::classification::Average;
+use crate::d_2::d_2_absolute_error_score;
+use crate::explained_variance::explained_variance_score;
+use crate::mean_absolute_error::mean_absolute_error;
+use crate::mean_absolute_percentage_error::mean_absolute_percentage_error;
+use crate::mean_squared_error::{mean_squared_error, root_mean_squared_error};
+use crate::mean_squared_log_error::mean_squared_log_error;
+use crate::median_absolute_error::{max_error, median_absolute_error};
+use crate::precision_recall::{f1_score, jaccard_score, precision_score, recall_score};
+use crate::precision_recall_curve::average_precision_score;
+use crate::probability::{brier_score_loss, log_loss};
+use crate::r_2::r_2;
+use crate::roc::roc_auc_score;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::{ErrString, RustLearnError};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+pub type ScoreFn =
+    Arc<dyn Fn(&NamedArray<f64>, &NamedArray<f64>) -> Result<f64, RustLearnError> + Send + Sync>;
+
+// a metric with what a search needs to rank by it; y_pred holds
+// probabilities or scores for the positive class when needs_proba is set
+#[derive(Clone)]
+pub struct RegisteredScorer {
+    pub name: String,
+    pub greater_is_better: bool,
+    pub needs_proba: bool,
+    metric: ScoreFn,
+}
+
+impl RegisteredScorer {
+    pub fn new<F>(name: &str, greater_is_better: bool, needs_proba: bool, metric: F) -> Self
+    where
+        F: Fn(&NamedArray<f64>, &NamedArray<f64>) -> Result<f64, RustLearnError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        RegisteredScorer {
+            name: name.to_string(),
+            greater_is_better,
+            needs_proba,
+            metric: Arc::new(metric),
+        }
+    }
+
+    // the value of the underlying metric
+    pub fn metric(
+        &self,
+        y_true: &NamedArray<f64>,
+        y_pred: &NamedArray<f64>,
+    ) -> Result<f64, RustLearnError> {
+        (self.metric)(y_true, y_pred)
+    }
+
+    // the metric negated when lower is better, so a larger score is always
+    // the better model
+    pub fn score(
+        &self,
+        y_true: &NamedArray<f64>,
+        y_pred: &NamedArray<f64>,
+    ) -> Result<f64, RustLearnError> {
+        let value = self.metric(y_true, y_pred)?;
+        Ok(if self.greater_is_better {
+            value
+        } else {
+            -value
+        })
+    }
+}
+
+impl fmt::Debug for RegisteredScorer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RegisteredScorer")
+            .field("name", &self.name)
+            .field("greater_is_better", &self.greater_is_better)
+            .field("needs_proba", &self.needs_proba)
+            .finish()
+    }
+}
+
+// the metric itself cannot be compared, so scorers are equal when their
+// names and flags are
+impl PartialEq for RegisteredScorer {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.greater_is_better == other.greater_is_better
+            && self.needs_proba == other.needs_proba
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScorerRegistry {
+    scorers: BTreeMap<String, RegisteredScorer>,
+}
+
+impl Default for ScorerRegistry {
+    fn default() -> Self {
+        let mut registry = ScorerRegistry::empty();
+        for scorer in builtin_scorers() {
+            registry.scorers.insert(scorer.name.clone(), scorer);
+        }
+        registry
+    }
+}
+
+impl ScorerRegistry {
+    // the built-in scorers
+    pub fn new() -> Self {
+        ScorerRegistry::default()
+    }
+
+    pub fn empty() -> Self {
+        ScorerRegistry {
+            scorers: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, scorer: RegisteredScorer) -> Result<(), RustLearnError> {
+        if self.scorers.contains_key(&scorer.name) {
+            return Err(RustLearnError::ValidationError(ErrString::from(format!(
+                "a scorer named {} is already registered",
+                scorer.name
+            ))));
+        }
+        self.scorers.insert(scorer.name.clone(), scorer);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Result<&RegisteredScorer, RustLearnError> {
+        self.scorers.get(name).ok_or_else(|| {
+            RustLearnError::ValidationError(ErrString::from(format!(
+                "unknown scorer {}, expected one of: {}",
+                name,
+                self.names().join(", ")
+            )))
+        })
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.scorers.contains_key(name)
+    }
+
+    // sorted
+    pub fn names(&self) -> Vec<String> {
+        self.scorers.keys().cloned().collect()
+    }
+
+    pub fn score(
+        &self,
+        name: &str,
+        y_true: &NamedArray<f64>,
+        y_pred: &NamedArray<f64>,
+    ) -> Result<f64, RustLearnError> {
+        self.get(name)?.score(y_true, y_pred)
+    }
+}
+
+// names follow scikit-learn; losses carry a neg_ prefix because their
+// score is the negated metric
+fn builtin_scorers() -> Vec<RegisteredScorer> {
+    vec![
+        // regression
+        RegisteredScorer::new("r2", true, false, |t, p| r_2(t.clone(), p.clone(), None)),
+        RegisteredScorer::new("explained_variance", true, false, |t, p| {
+            explained_variance_score(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new("d2_absolute_error_score", true, false, |t, p| {
+            d_2_absolute_error_score(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new("neg_mean_squared_error", false, false, |t, p| {
+            mean_squared_error(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new("neg_root_mean_squared_error", false, false, |t, p| {
+            root_mean_squared_error(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new("neg_mean_absolute_error", false, false, |t, p| {
+            mean_absolute_error(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new("neg_median_absolute_error", false, false, |t, p| {
+            median_absolute_error(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new(
+            "neg_mean_absolute_percentage_error",
+            false,
+            false,
+            |t, p| mean_absolute_percentage_error(t.clone(), p.clone(), None),
+        ),
+        RegisteredScorer::new("neg_mean_squared_log_error", false, false, |t, p| {
+            mean_squared_log_error(t.clone(), p.clone(), None)
+        }),
+        RegisteredScorer::new("neg_max_error", false, false, |t, p| {
+            max_error(t.clone(), p.clone())
+        }),
+        // classification on predicted labels
+        RegisteredScorer::new("accuracy", true, false, |t, p| {
+            accuracy_score(t.clone(), p.clone())
+        }),
+        RegisteredScorer::new("balanced_accuracy", true, false, |t, p| {
+            balanced_accuracy_score(t.clone(), p.clone())
+        }),
+        RegisteredScorer::new("precision", true, false, |t, p| {
+            precision_score(t.clone(), p.clone(), Average::Binary)
+        }),
+        RegisteredScorer::new("recall", true, false, |t, p| {
+            recall_score(t.clone(), p.clone(), Average::Binary)
+        }),
+        RegisteredScorer::new("f1", true, false, |t, p| {
+            f1_score(t.clone(), p.clone(), Average::Binary)
+        }),
+        RegisteredScorer::new("f1_macro", true, false, |t, p| {
+            f1_score(t.clone(), p.clone(), Average::Macro)
+        }),
+        RegisteredScorer::new("f1_weighted", true, false, |t, p| {
+            f1_score(t.clone(), p.clone(), Average::Weighted)
+        }),
+        RegisteredScorer::new("jaccard", true, false, |t, p| {
+            jaccard_score(t.clone(), p.clone(), Average::Binary)
+        }),
+        RegisteredScorer::new("matthews_corrcoef", true, false, |t, p| {
+            matthews_corrcoef(t.clone(), p.clone())
+        }),
+        RegisteredScorer::new("cohen_kappa", true, false, |t, p| {
+            cohen_kappa_score(t.clone(), p.clone())
+        }),
+        // classification on probabilities or scores
+        RegisteredScorer::new("roc_auc", true, true, |t, p| {
+            roc_auc_score(t.clone(), p.clone())
+        }),
+        RegisteredScorer::new("average_precision", true, true, |t, p| {
+            average_precision_score(t.clone(), p.clone())
+        }),
+        RegisteredScorer::new("neg_log_loss", false, true, |t, p| {
+            log_loss(t.clone(), p.clone(), 1e-15)
+        }),
+        RegisteredScorer::new("neg_brier_score", false, true, |t, p| {
+            brier_score_loss(t.clone(), p.clone())
+        }),
+        // clustering against known classes
+        RegisteredScorer::new("adjusted_rand_score", true, false, |t, p| {
+            adjusted_rand_score(t.clone(), p.clone())
+        }),
+        RegisteredScorer::new("adjusted_mutual_info_score", true, false, |t, p| {
+            adjusted_mutual_info_score(t.clone(), p.clone(), AverageMethod::Arithmetic)
+        }),
+        RegisteredScorer::new("normalized_mutual_info_score", true, false, |t, p| {
+            normalized_mutual_info_score(t.clone(), p.clone(), AverageMethod::Arithmetic)
+        }),
+        RegisteredScorer::new("v_measure_score", true, false, |t, p| {
+            v_measure_score(t.clone(), p.clone(), 1.0)
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[fixture]
+    fn predictions_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 5.0],
+        }
+    }
+
+    #[rstest]
+    fn test_builtin_scorers(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let registry = ScorerRegistry::new();
+        let mse = registry.get("neg_mean_squared_error").unwrap();
+        assert!(!mse.greater_is_better);
+        assert!(!mse.needs_proba);
+        assert_float_relative_eq!(
+            mse.metric(&target_named_array, &predictions_named_array)
+                .unwrap(),
+            4.0 / 3.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            mse.score(&target_named_array, &predictions_named_array)
+                .unwrap(),
+            -4.0 / 3.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            registry
+                .score("r2", &target_named_array, &predictions_named_array)
+                .unwrap(),
+            -1.0,
+            0.001
+        );
+        assert!(registry.get("roc_auc").unwrap().needs_proba);
+        assert!(registry.get("neg_log_loss").unwrap().needs_proba);
+        assert!(!registry.get("accuracy").unwrap().needs_proba);
+    }
+
+    #[rstest]
+    fn test_probability_scorer() {
+        let y_true = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 0.0, 1.0, 1.0],
+        };
+        let y_prob = NamedArray {
+            name: "probability".to_string(),
+            data: vec![0.1, 0.4, 0.35, 0.8],
+        };
+        let registry = ScorerRegistry::new();
+        assert_float_relative_eq!(
+            registry.score("roc_auc", &y_true, &y_prob).unwrap(),
+            0.75,
+            0.001
+        );
+    }
+
+    #[rstest]
+    fn test_register_custom(
+        target_named_array: NamedArray<f64>,
+        predictions_named_array: NamedArray<f64>,
+    ) {
+        let mut registry = ScorerRegistry::empty();
+        registry
+            .register(RegisteredScorer::new(
+                "neg_total_error",
+                false,
+                false,
+                |t, p| {
+                    Ok(t.data
+                        .iter()
+                        .zip(p.data.iter())
+                        .map(|(a, b)| (a - b).abs())
+                        .sum())
+                },
+            ))
+            .unwrap();
+        assert!(registry.contains("neg_total_error"));
+        assert_eq!(registry.names(), vec!["neg_total_error".to_string()]);
+        assert_float_relative_eq!(
+            registry
+                .score(
+                    "neg_total_error",
+                    &target_named_array,
+                    &predictions_named_array
+                )
+                .unwrap(),
+            -2.0,
+            0.001
+        );
+        let duplicate = RegisteredScorer::new("neg_total_error", false, false, |_, _| Ok(0.0));
+        assert!(registry.register(duplicate).is_err());
+    }
+
+    #[rstest]
+    fn test_unknown_scorer() {
+        let registry = ScorerRegistry::new();
+        assert!(registry.get("not_a_metric").is_err());
+        let names = registry.names();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+    }
+}
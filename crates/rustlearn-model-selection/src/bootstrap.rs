@@ -0,0 +1,396 @@
+use crate::cross_validation::CrossValidator;
+use crate::rng::seeded_rng;
+use crate::types::{take_rows, Fold};
+use num::ToPrimitive;
+use rand::Rng;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_array::stats::QuantileMethod;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+// each fold trains on a with-replacement draw of the rows (repeats kept) and
+// tests on the rows that draw left out of the bag
+#[derive(Debug, PartialEq, Clone)]
+pub struct Bootstrap {
+    pub n_bootstraps: usize,
+    pub random_state: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IntervalMethod {
+    Percentile,
+    BCa,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConfidenceInterval {
+    pub estimate: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub confidence: f64,
+    pub bootstrap_scores: Vec<f64>,
+}
+
+impl Bootstrap {
+    pub fn new(n_bootstraps: usize, random_state: Option<u64>) -> Result<Self> {
+        if n_bootstraps == 0 {
+            return Err(ValidationError(ErrString::from(
+                "n_bootstraps must be at least 1",
+            )));
+        }
+        Ok(Self {
+            n_bootstraps,
+            random_state,
+        })
+    }
+
+    pub fn samples(&self, n_rows: usize) -> Result<Vec<Fold>> {
+        if n_rows < 2 {
+            return Err(ValidationError(ErrString::from(
+                "bootstrapping needs at least 2 rows",
+            )));
+        }
+        let mut rng = seeded_rng(self.random_state);
+        let mut samples: Vec<Fold> = Vec::new();
+        while samples.len() < self.n_bootstraps {
+            let mut train: Vec<usize> = (0..n_rows).map(|_| rng.gen_range(0..n_rows)).collect();
+            train.sort();
+            let mut in_bag = vec![false; n_rows];
+            for i in train.iter() {
+                in_bag[*i] = true;
+            }
+            let test: Vec<usize> = (0..n_rows).filter(|i| !in_bag[*i]).collect();
+            // a draw that hits every row leaves nothing to test on
+            if !test.is_empty() {
+                samples.push(Fold { train, test });
+            }
+        }
+        Ok(samples)
+    }
+}
+
+impl CrossValidator for Bootstrap {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        self.samples(y.data.len())
+    }
+}
+
+// Abramowitz and Stegun 7.1.26, accurate to about 1e-7
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+// Acklam's rational approximation of the normal quantile function
+fn normal_ppf(p: f64) -> f64 {
+    let a = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    let b = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    let c = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    let d = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    let p_low = 0.02425;
+    if p < p_low {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((c[0] * q + c[1]) * q + c[2]) * q + c[3]) * q + c[4]) * q + c[5])
+            / ((((d[0] * q + d[1]) * q + d[2]) * q + d[3]) * q + 1.0)
+    } else if p > 1.0 - p_low {
+        -normal_ppf(1.0 - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((a[0] * r + a[1]) * r + a[2]) * r + a[3]) * r + a[4]) * r + a[5]) * q
+            / (((((b[0] * r + b[1]) * r + b[2]) * r + b[3]) * r + b[4]) * r + 1.0)
+    }
+}
+
+// jackknife estimate of how fast the metric's standard error changes with
+// its value
+fn acceleration<F>(y_true: &NamedArray<f64>, y_pred: &NamedArray<f64>, metric: &F) -> Result<f64>
+where
+    F: Fn(NamedArray<f64>, NamedArray<f64>) -> Result<f64>,
+{
+    let n_rows = y_true.data.len();
+    let mut jackknife: Vec<f64> = Vec::new();
+    for left_out in 0..n_rows {
+        let rows: Vec<usize> = (0..n_rows).filter(|i| *i != left_out).collect();
+        jackknife.push(metric(take_rows(y_true, &rows), take_rows(y_pred, &rows))?);
+    }
+    let mean = jackknife.iter().sum::<f64>() / n_rows as f64;
+    let num: f64 = jackknife.iter().map(|v| (mean - v).powi(3)).sum();
+    let den: f64 = jackknife.iter().map(|v| (mean - v).powi(2)).sum();
+    if den == 0.0 {
+        return Ok(0.0);
+    }
+    Ok(num / (6.0 * den.powf(1.5)))
+}
+
+pub fn bootstrap_confidence_interval<T, F>(
+    y_true: &NamedArray<T>,
+    y_pred: &NamedArray<T>,
+    metric: F,
+    bootstrap: &Bootstrap,
+    confidence: f64,
+    method: IntervalMethod,
+) -> Result<ConfidenceInterval>
+where
+    T: ToPrimitive,
+    F: Fn(NamedArray<f64>, NamedArray<f64>) -> Result<f64>,
+{
+    if confidence <= 0.0 || confidence >= 1.0 {
+        return Err(ValidationError(ErrString::from(
+            "confidence must be between 0 and 1",
+        )));
+    }
+    if y_true.data.len() != y_pred.data.len() {
+        return Err(ValidationError(ErrString::from(
+            "series are not the same length",
+        )));
+    }
+
+    let all_rows: Vec<usize> = (0..y_true.data.len()).collect();
+    let (y_true, y_pred) = (take_rows(y_true, &all_rows), take_rows(y_pred, &all_rows));
+    let estimate = metric(y_true.clone(), y_pred.clone())?;
+    let mut scores: Vec<f64> = Vec::new();
+    for sample in bootstrap.samples(y_true.data.len())? {
+        scores.push(metric(
+            take_rows(&y_true, &sample.train),
+            take_rows(&y_pred, &sample.train),
+        )?);
+    }
+    let distribution = NamedArray {
+        name: "bootstrap_scores".to_string(),
+        data: scores,
+    };
+
+    let alpha = (1.0 - confidence) / 2.0;
+    let (q_lower, q_upper) = match method {
+        IntervalMethod::Percentile => (alpha, 1.0 - alpha),
+        IntervalMethod::BCa => {
+            let below = distribution.data.iter().filter(|s| **s < estimate).count();
+            let proportion = below as f64 / distribution.data.len() as f64;
+            if proportion == 0.0 || proportion == 1.0 {
+                return Err(ValidationError(ErrString::from(
+                    "bootstrap scores all fall on one side of the estimate, cannot correct for bias",
+                )));
+            }
+            let z0 = normal_ppf(proportion);
+            let a = acceleration(&y_true, &y_pred, &metric)?;
+            let adjust = |z: f64| normal_cdf(z0 + (z0 + z) / (1.0 - a * (z0 + z)));
+            (adjust(normal_ppf(alpha)), adjust(normal_ppf(1.0 - alpha)))
+        }
+    };
+    let bounds = distribution.quantiles(&[q_lower, q_upper], QuantileMethod::Linear)?;
+
+    Ok(ConfidenceInterval {
+        estimate,
+        lower: bounds[0],
+        upper: bounds[1],
+        confidence,
+        bootstrap_scores: distribution.data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_validate::cross_validate;
+    use crate::cross_validate::tests::MeanEstimator;
+    use crate::scoring::Scorer;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+    use rustlearn_metrics::mean_absolute_error::mean_absolute_error;
+    use rustlearn_metrics::r_2::r_2;
+
+    #[fixture]
+    fn y_true() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: (0..40).map(|i| i as f64).collect(),
+        }
+    }
+
+    #[fixture]
+    fn y_pred() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: (0..40)
+                .map(|i| i as f64 + [1.5, -0.5, 2.0, -3.0, 0.5][i % 5])
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    fn test_normal_helpers() {
+        assert_float_relative_eq!(normal_cdf(0.0), 0.5, 0.001);
+        assert_float_relative_eq!(normal_cdf(1.959964), 0.975, 0.001);
+        assert_float_relative_eq!(normal_cdf(-1.0), 0.158655, 0.001);
+        assert_float_relative_eq!(normal_ppf(0.975), 1.959964, 0.001);
+        assert_float_relative_eq!(normal_ppf(0.01), -2.326348, 0.001);
+        assert_float_relative_eq!(normal_ppf(0.5), 0.0, 0.001);
+    }
+
+    #[rstest]
+    fn test_samples() {
+        let bootstrap = Bootstrap::new(20, Some(4)).unwrap();
+        let samples = bootstrap.samples(10).unwrap();
+        assert_eq!(samples.len(), 20);
+        for sample in samples.iter() {
+            assert_eq!(sample.train.len(), 10);
+            for row in sample.test.iter() {
+                assert!(!sample.train.contains(row));
+            }
+            for row in 0..10 {
+                assert!(sample.train.contains(&row) || sample.test.contains(&row));
+            }
+        }
+        assert!(samples.iter().all(|s| !s.test.is_empty()));
+        assert_eq!(samples, bootstrap.samples(10).unwrap());
+    }
+
+    #[rstest]
+    fn test_samples_leave_rows_out() {
+        // with two rows half of all draws take both, which would leave an
+        // empty test fold
+        let samples = Bootstrap::new(20, Some(0)).unwrap().samples(2).unwrap();
+        assert_eq!(samples.len(), 20);
+        assert!(samples.iter().all(|s| s.test.len() == 1));
+    }
+
+    #[rstest]
+    fn test_samples_errors() {
+        assert!(Bootstrap::new(0, None).is_err());
+        assert!(Bootstrap::new(5, None).unwrap().samples(1).is_err());
+    }
+
+    #[rstest]
+    fn test_out_of_bag_evaluation(y_true: NamedArray<f64>) {
+        let x = vec![y_true.clone()];
+        let res = cross_validate(
+            &MeanEstimator::default(),
+            &x,
+            &y_true,
+            &Bootstrap::new(5, Some(0)).unwrap(),
+            &[Scorer::MeanSquaredError],
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.test_scores["mean_squared_error"].len(), 5);
+    }
+
+    #[rstest]
+    fn test_percentile_interval(y_true: NamedArray<f64>, y_pred: NamedArray<f64>) {
+        let bootstrap = Bootstrap::new(500, Some(1)).unwrap();
+        let ci = bootstrap_confidence_interval(
+            &y_true,
+            &y_pred,
+            |t, p| mean_absolute_error(t, p, None),
+            &bootstrap,
+            0.9,
+            IntervalMethod::Percentile,
+        )
+        .unwrap();
+
+        assert_float_relative_eq!(ci.estimate, 1.5, 0.001);
+        assert!(ci.lower < ci.estimate && ci.estimate < ci.upper);
+        assert_eq!(ci.bootstrap_scores.len(), 500);
+        let distribution = NamedArray {
+            name: "scores".to_string(),
+            data: ci.bootstrap_scores.clone(),
+        };
+        assert_eq!(
+            ci.lower,
+            distribution.quantile(0.05, QuantileMethod::Linear).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_bca_interval(y_true: NamedArray<f64>, y_pred: NamedArray<f64>) {
+        let bootstrap = Bootstrap::new(500, Some(1)).unwrap();
+        let percentile = bootstrap_confidence_interval(
+            &y_true,
+            &y_pred,
+            |t, p| r_2(t, p, None),
+            &bootstrap,
+            0.95,
+            IntervalMethod::Percentile,
+        )
+        .unwrap();
+        let bca = bootstrap_confidence_interval(
+            &y_true,
+            &y_pred,
+            |t, p| r_2(t, p, None),
+            &bootstrap,
+            0.95,
+            IntervalMethod::BCa,
+        )
+        .unwrap();
+
+        assert_eq!(bca.estimate, percentile.estimate);
+        assert_eq!(bca.bootstrap_scores, percentile.bootstrap_scores);
+        assert!(bca.lower < bca.estimate && bca.estimate < bca.upper);
+        assert!(bca.upper <= 1.0);
+    }
+
+    #[rstest]
+    fn test_interval_errors(y_true: NamedArray<f64>, y_pred: NamedArray<f64>) {
+        let bootstrap = Bootstrap::new(50, Some(1)).unwrap();
+        assert!(bootstrap_confidence_interval(
+            &y_true,
+            &y_pred,
+            |t, p| r_2(t, p, None),
+            &bootstrap,
+            1.0,
+            IntervalMethod::Percentile,
+        )
+        .is_err());
+
+        // a perfect model scores the same on every resample
+        assert!(bootstrap_confidence_interval(
+            &y_true,
+            &y_true,
+            |t, p| mean_absolute_error(t, p, None),
+            &bootstrap,
+            0.9,
+            IntervalMethod::BCa,
+        )
+        .is_err());
+    }
+}
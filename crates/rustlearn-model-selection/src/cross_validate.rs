@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::cross_validation::{materialize_folds, CrossValidator};
+use crate::estimator::Estimator;
+use crate::scoring::Scorer;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CrossValidateReturn {
+    pub test_scores: HashMap<String, Vec<f64>>,
+    pub fit_times: Vec<f64>,
+    pub score_times: Vec<f64>,
+    pub predictions: Option<NamedArray<f64>>,
+}
+
+pub fn cross_validate<E, S, T>(
+    estimator: &E,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    scorers: &[Scorer],
+    return_predictions: bool,
+) -> Result<CrossValidateReturn>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive,
+{
+    if scorers.is_empty() {
+        return Err(ValidationError(ErrString::from("no scorers provided")));
+    }
+    let folds = splitter.split(y)?;
+    let splits = materialize_folds(x, y, &folds)?;
+
+    let mut test_scores: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut fit_times: Vec<f64> = Vec::new();
+    let mut score_times: Vec<f64> = Vec::new();
+    // rows that never land in a test fold keep a NaN prediction
+    let mut predictions: Vec<f64> = vec![f64::NAN; y.data.len()];
+    let mut predicted = vec![false; y.data.len()];
+
+    for (fold, split) in folds.iter().zip(splits.iter()) {
+        let mut model = estimator.clone();
+        let start = Instant::now();
+        model.fit(&split.x_train, &split.y_train)?;
+        fit_times.push(start.elapsed().as_secs_f64());
+
+        let start = Instant::now();
+        let y_pred = model.predict(&split.x_test)?;
+        for scorer in scorers.iter() {
+            test_scores
+                .entry(scorer.name().to_string())
+                .or_default()
+                .push(scorer.score(&split.y_test, &y_pred)?);
+        }
+        score_times.push(start.elapsed().as_secs_f64());
+
+        if return_predictions {
+            for (row, value) in fold.test.iter().zip(y_pred.data.iter()) {
+                if predicted[*row] {
+                    return Err(ValidationError(ErrString::from(
+                        "out-of-fold predictions need every row in at most one test fold",
+                    )));
+                }
+                predicted[*row] = true;
+                predictions[*row] = *value;
+            }
+        }
+    }
+
+    Ok(CrossValidateReturn {
+        test_scores,
+        fit_times,
+        score_times,
+        predictions: return_predictions.then(|| NamedArray {
+            name: "predictions".to_string(),
+            data: predictions,
+        }),
+    })
+}
+
+pub fn cross_val_score<E, S, T>(
+    estimator: &E,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    scorer: &Scorer,
+) -> Result<Vec<f64>>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive,
+{
+    let mut res = cross_validate(
+        estimator,
+        x,
+        y,
+        splitter,
+        std::slice::from_ref(scorer),
+        false,
+    )?;
+    Ok(res.test_scores.remove(scorer.name()).unwrap())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::cross_validation::kfold::{KFold, RepeatedKFold};
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+    use rustlearn_metrics::scorer::RegisteredScorer;
+
+    // predicts the training mean pulled towards zero by `shrinkage`, enough
+    // to check the bookkeeping
+    #[derive(Debug, PartialEq, Clone, Default)]
+    pub struct MeanEstimator {
+        pub mean: f64,
+        pub shrinkage: f64,
+    }
+
+    impl Estimator for MeanEstimator {
+        fn fit(&mut self, _x: &[NamedArray<f64>], y: &NamedArray<f64>) -> Result<()> {
+            self.mean = y.mean()?;
+            Ok(())
+        }
+
+        fn predict(&self, x: &[NamedArray<f64>]) -> Result<NamedArray<f64>> {
+            Ok(NamedArray {
+                name: "predictions".to_string(),
+                data: vec![self.mean * (1.0 - self.shrinkage); x[0].data.len()],
+            })
+        }
+
+        fn set_param(&mut self, name: &str, value: f64) -> Result<()> {
+            match name {
+                "shrinkage" => {
+                    self.shrinkage = value;
+                    Ok(())
+                }
+                _ => Err(ValidationError(ErrString::from(format!(
+                    "estimator has no parameter named {name}"
+                )))),
+            }
+        }
+    }
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }
+    }
+
+    #[rstest]
+    fn test_cross_validate(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let res = cross_validate(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            &[Scorer::MeanSquaredError, Scorer::MeanAbsoluteError],
+            true,
+        )
+        .unwrap();
+
+        let mse = res.test_scores.get("mean_squared_error").unwrap();
+        assert_eq!(mse.len(), 2);
+        for v in mse.iter() {
+            assert_float_relative_eq!(*v, 4.25, 0.001);
+        }
+        let mae = res.test_scores.get("mean_absolute_error").unwrap();
+        assert_float_relative_eq!(mae[0], 2.0, 0.001);
+        assert_eq!(res.fit_times.len(), 2);
+        assert_eq!(res.score_times.len(), 2);
+        assert_eq!(res.predictions.unwrap().data, vec![3.5, 3.5, 1.5, 1.5]);
+    }
+
+    #[rstest]
+    fn test_cross_val_score(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let scores = cross_val_score(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            &Scorer::MeanAbsoluteError,
+        )
+        .unwrap();
+        assert_eq!(scores, vec![2.0, 2.0]);
+    }
+
+    #[rstest]
+    fn test_cross_validate_errors(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        assert!(cross_validate(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            &[],
+            false,
+        )
+        .is_err());
+
+        // repeated folds test every row more than once
+        let repeated = RepeatedKFold::new(2, 2, Some(0)).unwrap();
+        assert!(cross_validate(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &repeated,
+            &[Scorer::R2],
+            true,
+        )
+        .is_err());
+        assert!(cross_validate(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &repeated,
+            &[Scorer::R2],
+            false,
+        )
+        .is_ok());
+    }
+
+    // passes the feature through as its prediction, a stand-in for a
+    // classifier's positive class scores
+    #[derive(Debug, PartialEq, Clone, Default)]
+    struct FeatureEstimator;
+
+    impl Estimator for FeatureEstimator {
+        fn fit(&mut self, _x: &[NamedArray<f64>], _y: &NamedArray<f64>) -> Result<()> {
+            Ok(())
+        }
+
+        fn predict(&self, x: &[NamedArray<f64>]) -> Result<NamedArray<f64>> {
+            Ok(x[0].clone())
+        }
+    }
+
+    #[rstest]
+    fn test_registered_scorers() {
+        let x = vec![NamedArray {
+            name: "score".to_string(),
+            data: vec![0.1, 0.4, 0.35, 0.8, 0.2, 0.9],
+        }];
+        let y = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 0.0, 1.0, 1.0, 0.0, 1.0],
+        };
+        let n_positive = RegisteredScorer::new("n_positive", true, false, |t, _| {
+            Ok(t.data.iter().filter(|v| **v > 0.0).count() as f64)
+        });
+        let res = cross_validate(
+            &FeatureEstimator,
+            &x,
+            &y,
+            &KFold::new(2).unwrap(),
+            &[
+                Scorer::from_name("roc_auc").unwrap(),
+                Scorer::from(n_positive),
+                Scorer::MeanAbsoluteError,
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.test_scores["roc_auc"], vec![0.5, 1.0]);
+        assert_eq!(res.test_scores["n_positive"], vec![1.0, 2.0]);
+        assert_eq!(res.test_scores["mean_absolute_error"].len(), 2);
+
+        // losses from the registry are negated
+        let scores = cross_val_score(
+            &MeanEstimator::default(),
+            &x,
+            &y,
+            &KFold::new(2).unwrap(),
+            &Scorer::from_name("neg_mean_absolute_error").unwrap(),
+        )
+        .unwrap();
+        let losses = cross_val_score(
+            &MeanEstimator::default(),
+            &x,
+            &y,
+            &KFold::new(2).unwrap(),
+            &Scorer::MeanAbsoluteError,
+        )
+        .unwrap();
+        for (score, loss) in scores.iter().zip(losses) {
+            assert_float_relative_eq!(*score, -loss, 0.001);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::cross_validation::{assert_min_n_splits, CrossValidator, Result};
+use crate::train_test_split::stratified::class_indices;
+use crate::types::Fold;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GroupKFold<G> {
+    pub groups: NamedArray<G>,
+    pub n_splits: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LeavePGroupsOut<G> {
+    pub groups: NamedArray<G>,
+    pub n_groups: usize,
+}
+
+fn assert_groups_match<G, T>(groups: &NamedArray<G>, y: &NamedArray<T>) -> Result<()> {
+    if groups.data.len() != y.data.len() {
+        return Err(ValidationError(ErrString::from(
+            "mismatch groups and y lengths",
+        )));
+    }
+    Ok(())
+}
+
+// every combination of `size` elements out of 0..n, in lexicographic order
+fn combinations(n: usize, size: usize) -> Vec<Vec<usize>> {
+    let mut result: Vec<Vec<usize>> = Vec::new();
+    let mut current: Vec<usize> = (0..size).collect();
+    loop {
+        result.push(current.clone());
+        let mut i = size;
+        while i > 0 && current[i - 1] == n - size + i - 1 {
+            i -= 1;
+        }
+        if i == 0 {
+            return result;
+        }
+        current[i - 1] += 1;
+        for j in i..size {
+            current[j] = current[j - 1] + 1;
+        }
+    }
+}
+
+impl<G> GroupKFold<G>
+where
+    G: ToPrimitive,
+{
+    pub fn new(groups: NamedArray<G>, n_splits: usize) -> Result<Self> {
+        assert_min_n_splits(n_splits)?;
+        let n_unique = class_indices(&groups).len();
+        if n_splits > n_unique {
+            return Err(ValidationError(ErrString::from(format!(
+                "cannot have n_splits={n_splits} greater than the number of groups ({n_unique})"
+            ))));
+        }
+        Ok(Self { groups, n_splits })
+    }
+}
+
+impl<G> CrossValidator for GroupKFold<G>
+where
+    G: ToPrimitive,
+{
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        assert_groups_match(&self.groups, y)?;
+
+        // largest groups first, each into the currently lightest fold
+        let mut groups = class_indices(&self.groups);
+        groups.sort_by_key(|g| std::cmp::Reverse(g.1.len()));
+        let mut tests: Vec<Vec<usize>> = vec![Vec::new(); self.n_splits];
+        for (_, rows) in groups {
+            let lightest = (0..self.n_splits).min_by_key(|k| tests[*k].len()).unwrap();
+            tests[lightest].extend(rows);
+        }
+        Ok(tests
+            .into_iter()
+            .map(|test| Fold::from_test(test, y.data.len()))
+            .collect())
+    }
+}
+
+impl<G> LeavePGroupsOut<G>
+where
+    G: ToPrimitive,
+{
+    pub fn new(groups: NamedArray<G>, n_groups: usize) -> Result<Self> {
+        if n_groups == 0 {
+            return Err(ValidationError(ErrString::from(
+                "n_groups must be at least 1",
+            )));
+        }
+        let n_unique = class_indices(&groups).len();
+        if n_groups >= n_unique {
+            return Err(ValidationError(ErrString::from(format!(
+                "n_groups={n_groups} must be smaller than the number of groups ({n_unique})"
+            ))));
+        }
+        Ok(Self { groups, n_groups })
+    }
+}
+
+impl<G> CrossValidator for LeavePGroupsOut<G>
+where
+    G: ToPrimitive,
+{
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        assert_groups_match(&self.groups, y)?;
+        let groups = class_indices(&self.groups);
+        Ok(combinations(groups.len(), self.n_groups)
+            .iter()
+            .map(|held_out| {
+                let test = held_out.iter().flat_map(|g| groups[*g].1.clone()).collect();
+                Fold::from_test(test, y.data.len())
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn patients() -> NamedArray<i32> {
+        NamedArray {
+            name: "patient".to_string(),
+            data: vec![1, 1, 1, 1, 2, 2, 3, 3, 3, 4],
+        }
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: (0..10).map(|i| i as f64).collect(),
+        }
+    }
+
+    #[rstest]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(4, 2),
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+        assert_eq!(combinations(3, 3), vec![vec![0, 1, 2]]);
+    }
+
+    #[rstest]
+    fn test_group_kfold(patients: NamedArray<i32>, target_named_array: NamedArray<f64>) {
+        let folds = GroupKFold::new(patients.clone(), 2)
+            .unwrap()
+            .split(&target_named_array)
+            .unwrap();
+        assert_eq!(folds.len(), 2);
+        // patient 1 (4 rows) pairs with patient 4 (1 row) against 3 and 2
+        assert_eq!(folds[0].test, vec![0, 1, 2, 3, 9]);
+        assert_eq!(folds[1].test, vec![4, 5, 6, 7, 8]);
+
+        for fold in folds.iter() {
+            for test_row in fold.test.iter() {
+                for train_row in fold.train.iter() {
+                    assert_ne!(patients.data[*test_row], patients.data[*train_row]);
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_group_kfold_errors(patients: NamedArray<i32>) {
+        assert!(GroupKFold::new(patients.clone(), 5).is_err());
+        assert!(GroupKFold::new(patients.clone(), 1).is_err());
+        let short = NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0],
+        };
+        assert!(GroupKFold::new(patients, 2).unwrap().split(&short).is_err());
+    }
+
+    #[rstest]
+    fn test_leave_p_groups_out(patients: NamedArray<i32>, target_named_array: NamedArray<f64>) {
+        let folds = LeavePGroupsOut::new(patients, 2)
+            .unwrap()
+            .split(&target_named_array)
+            .unwrap();
+        assert_eq!(folds.len(), 6);
+        assert_eq!(folds[0].test, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(folds[5].test, vec![6, 7, 8, 9]);
+        assert_eq!(folds[5].train, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[rstest]
+    fn test_leave_p_groups_out_errors(patients: NamedArray<i32>) {
+        assert!(LeavePGroupsOut::new(patients.clone(), 0).is_err());
+        assert!(LeavePGroupsOut::new(patients, 4).is_err());
+    }
+}
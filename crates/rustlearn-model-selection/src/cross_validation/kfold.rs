@@ -0,0 +1,172 @@
+use crate::cross_validation::{
+    assert_logical_n_splits, assert_min_n_splits, CrossValidator, Result,
+};
+use crate::rng::seeded_rng;
+use crate::types::Fold;
+use num::ToPrimitive;
+use rand::seq::SliceRandom;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct KFold {
+    pub n_splits: usize,
+    pub shuffle: bool,
+    pub random_state: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RepeatedKFold {
+    pub n_splits: usize,
+    pub n_repeats: usize,
+    pub random_state: Option<u64>,
+}
+
+// cuts the rows into n_splits consecutive chunks, the first n_rows % n_splits
+// chunks taking one extra row
+pub fn contiguous_folds(order: &[usize], n_splits: usize) -> Vec<Fold> {
+    let n_rows = order.len();
+    let mut folds: Vec<Fold> = Vec::new();
+    let mut start = 0;
+    for k in 0..n_splits {
+        let size = n_rows / n_splits + usize::from(k < n_rows % n_splits);
+        folds.push(Fold::from_test(order[start..start + size].to_vec(), n_rows));
+        start += size;
+    }
+    folds
+}
+
+impl KFold {
+    pub fn new(n_splits: usize) -> Result<Self> {
+        assert_min_n_splits(n_splits)?;
+        Ok(Self {
+            n_splits,
+            shuffle: false,
+            random_state: None,
+        })
+    }
+
+    pub fn with_shuffle(mut self, random_state: Option<u64>) -> Self {
+        self.shuffle = true;
+        self.random_state = random_state;
+        self
+    }
+}
+
+impl CrossValidator for KFold {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        assert_logical_n_splits(self.n_splits, y.data.len())?;
+        let mut order: Vec<usize> = (0..y.data.len()).collect();
+        if self.shuffle {
+            order.shuffle(&mut seeded_rng(self.random_state));
+        }
+        Ok(contiguous_folds(&order, self.n_splits))
+    }
+}
+
+impl RepeatedKFold {
+    pub fn new(n_splits: usize, n_repeats: usize, random_state: Option<u64>) -> Result<Self> {
+        assert_min_n_splits(n_splits)?;
+        if n_repeats == 0 {
+            return Err(ValidationError(ErrString::from(
+                "n_repeats must be at least 1",
+            )));
+        }
+        Ok(Self {
+            n_splits,
+            n_repeats,
+            random_state,
+        })
+    }
+}
+
+impl CrossValidator for RepeatedKFold {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        assert_logical_n_splits(self.n_splits, y.data.len())?;
+        let mut rng = seeded_rng(self.random_state);
+        let mut folds: Vec<Fold> = Vec::new();
+        for _ in 0..self.n_repeats {
+            let mut order: Vec<usize> = (0..y.data.len()).collect();
+            order.shuffle(&mut rng);
+            folds.extend(contiguous_folds(&order, self.n_splits));
+        }
+        Ok(folds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: (0..10).map(|i| i as f64).collect(),
+        }
+    }
+
+    fn assert_partition(folds: &[Fold], n_rows: usize) {
+        let mut seen: Vec<usize> = folds.iter().flat_map(|f| f.test.clone()).collect();
+        seen.sort();
+        assert_eq!(seen, (0..n_rows).collect::<Vec<usize>>());
+        for fold in folds.iter() {
+            assert_eq!(fold.train.len() + fold.test.len(), n_rows);
+        }
+    }
+
+    #[rstest]
+    fn test_kfold(target_named_array: NamedArray<f64>) {
+        let folds = KFold::new(3).unwrap().split(&target_named_array).unwrap();
+        assert_eq!(folds.len(), 3);
+        assert_eq!(folds[0].test, vec![0, 1, 2, 3]);
+        assert_eq!(folds[1].test, vec![4, 5, 6]);
+        assert_eq!(folds[2].test, vec![7, 8, 9]);
+        assert_eq!(folds[2].train, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_partition(&folds, 10);
+    }
+
+    #[rstest]
+    fn test_kfold_shuffle(target_named_array: NamedArray<f64>) {
+        let kfold = KFold::new(5).unwrap().with_shuffle(Some(42));
+        let folds = kfold.split(&target_named_array).unwrap();
+        assert_eq!(folds, kfold.split(&target_named_array).unwrap());
+        assert_ne!(
+            folds,
+            KFold::new(5).unwrap().split(&target_named_array).unwrap()
+        );
+        assert_partition(&folds, 10);
+    }
+
+    #[rstest]
+    fn test_kfold_errors(target_named_array: NamedArray<f64>) {
+        assert!(KFold::new(1).is_err());
+        assert!(KFold::new(11).unwrap().split(&target_named_array).is_err());
+    }
+
+    #[rstest]
+    fn test_repeated_kfold(target_named_array: NamedArray<f64>) {
+        let repeated = RepeatedKFold::new(5, 3, Some(0)).unwrap();
+        let folds = repeated.split(&target_named_array).unwrap();
+        assert_eq!(folds.len(), 15);
+        for repeat in folds.chunks(5) {
+            assert_partition(repeat, 10);
+        }
+        assert_ne!(folds[0..5], folds[5..10]);
+        assert_eq!(folds, repeated.split(&target_named_array).unwrap());
+    }
+
+    #[rstest]
+    fn test_repeated_kfold_errors() {
+        assert!(RepeatedKFold::new(1, 3, None).is_err());
+        assert!(RepeatedKFold::new(2, 0, None).is_err());
+    }
+}
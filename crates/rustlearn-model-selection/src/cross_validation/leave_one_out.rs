@@ -0,0 +1,73 @@
+use crate::cross_validation::{CrossValidator, Result};
+use crate::types::Fold;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LeaveOneOut {}
+
+impl LeaveOneOut {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CrossValidator for LeaveOneOut {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        let n_rows = y.data.len();
+        if n_rows < 2 {
+            return Err(ValidationError(ErrString::from(
+                "leave one out needs at least 2 rows",
+            )));
+        }
+        Ok((0..n_rows)
+            .map(|i| Fold::from_test(vec![i], n_rows))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn test_leave_one_out() {
+        let y = NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        };
+        let folds = LeaveOneOut::new().split(&y).unwrap();
+        assert_eq!(
+            folds,
+            vec![
+                Fold {
+                    train: vec![1, 2],
+                    test: vec![0]
+                },
+                Fold {
+                    train: vec![0, 2],
+                    test: vec![1]
+                },
+                Fold {
+                    train: vec![0, 1],
+                    test: vec![2]
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_leave_one_out_too_small() {
+        let y = NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0],
+        };
+        assert!(LeaveOneOut::new().split(&y).is_err());
+    }
+}
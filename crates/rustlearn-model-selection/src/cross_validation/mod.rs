@@ -0,0 +1,136 @@
+pub mod group_kfold;
+pub mod kfold;
+pub mod leave_one_out;
+pub mod stratified_kfold;
+pub mod time_series;
+
+use crate::types::{Fold, TrainTestSplitReturn};
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+pub trait CrossValidator {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive;
+}
+
+pub fn assert_min_n_splits(n_splits: usize) -> Result<()> {
+    if n_splits < 2 {
+        return Err(ValidationError(ErrString::from(
+            "n_splits must be at least 2",
+        )));
+    }
+    Ok(())
+}
+
+pub fn assert_logical_n_splits(n_splits: usize, n_rows: usize) -> Result<()> {
+    assert_min_n_splits(n_splits)?;
+    if n_splits > n_rows {
+        return Err(ValidationError(ErrString::from(format!(
+            "cannot have n_splits={n_splits} greater than the number of rows ({n_rows})"
+        ))));
+    }
+    Ok(())
+}
+
+pub fn materialize_folds<T>(
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    folds: &[Fold],
+) -> Result<Vec<TrainTestSplitReturn>>
+where
+    T: ToPrimitive,
+{
+    for nm in x.iter() {
+        if nm.data.len() != y.data.len() {
+            return Err(ValidationError(ErrString::from("mismatch x and y lengths")));
+        }
+    }
+    for fold in folds.iter() {
+        if fold
+            .train
+            .iter()
+            .chain(fold.test.iter())
+            .any(|i| *i >= y.data.len())
+        {
+            return Err(ValidationError(ErrString::from(
+                "fold refers to a row outside of the data",
+            )));
+        }
+    }
+    Ok(folds
+        .iter()
+        .map(|fold| TrainTestSplitReturn::from_indices(x, y, &fold.train, &fold.test))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::kfold::KFold;
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![10.0, 20.0, 30.0, 40.0],
+        }
+    }
+
+    #[rstest]
+    fn test_fold_from_test() {
+        let fold = Fold::from_test(vec![3, 1], 5);
+        assert_eq!(fold.test, vec![1, 3]);
+        assert_eq!(fold.train, vec![0, 2, 4]);
+    }
+
+    #[rstest]
+    fn test_materialize_folds(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let folds = KFold::new(2).unwrap().split(&target_named_array).unwrap();
+        let res = materialize_folds(&input_named_array, &target_named_array, &folds).unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].x_test[0].data, vec![1.0, 2.0]);
+        assert_eq!(res[0].y_train.data, vec![30.0, 40.0]);
+        assert_eq!(res[1].x_train[0].data, vec![1.0, 2.0]);
+        assert_eq!(res[1].y_test.data, vec![30.0, 40.0]);
+    }
+
+    #[rstest]
+    fn test_materialize_folds_errors(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let folds = vec![Fold::from_test(vec![4], 5)];
+        assert!(materialize_folds(&input_named_array, &target_named_array, &folds).is_err());
+
+        let short = NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0],
+        };
+        assert!(materialize_folds(&input_named_array, &short, &[]).is_err());
+    }
+
+    #[rstest]
+    fn test_assert_logical_n_splits() {
+        assert!(assert_logical_n_splits(1, 10).is_err());
+        assert!(assert_logical_n_splits(11, 10).is_err());
+        assert!(assert_logical_n_splits(10, 10).is_ok());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::cross_validation::{
+    assert_logical_n_splits, assert_min_n_splits, CrossValidator, Result,
+};
+use crate::rng::seeded_rng;
+use crate::train_test_split::stratified::class_indices;
+use crate::types::Fold;
+use num::ToPrimitive;
+use rand::seq::SliceRandom;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct StratifiedKFold {
+    pub n_splits: usize,
+    pub shuffle: bool,
+    pub random_state: Option<u64>,
+}
+
+impl StratifiedKFold {
+    pub fn new(n_splits: usize) -> Result<Self> {
+        assert_min_n_splits(n_splits)?;
+        Ok(Self {
+            n_splits,
+            shuffle: false,
+            random_state: None,
+        })
+    }
+
+    pub fn with_shuffle(mut self, random_state: Option<u64>) -> Self {
+        self.shuffle = true;
+        self.random_state = random_state;
+        self
+    }
+}
+
+impl CrossValidator for StratifiedKFold {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        let n_rows = y.data.len();
+        assert_logical_n_splits(self.n_splits, n_rows)?;
+        let mut rng = seeded_rng(self.random_state);
+
+        // lay the classes out one after another and deal the rows round robin,
+        // so every fold gets its share of each class and fold sizes differ by
+        // at most one row
+        let mut order: Vec<usize> = Vec::new();
+        for (label, mut rows) in class_indices(y) {
+            if rows.len() < self.n_splits {
+                return Err(ValidationError(ErrString::from(format!(
+                    "class {label} has {} members, fewer than n_splits={}",
+                    rows.len(),
+                    self.n_splits
+                ))));
+            }
+            if self.shuffle {
+                rows.shuffle(&mut rng);
+            }
+            order.extend(rows);
+        }
+
+        let mut tests: Vec<Vec<usize>> = vec![Vec::new(); self.n_splits];
+        for (position, row) in order.iter().enumerate() {
+            tests[position % self.n_splits].push(*row);
+        }
+        Ok(tests
+            .into_iter()
+            .map(|test| Fold::from_test(test, n_rows))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn imbalanced_target() -> NamedArray<f64> {
+        let mut data = vec![0.0; 12];
+        for i in [1, 5, 9] {
+            data[i] = 1.0;
+        }
+        NamedArray {
+            name: "target".to_string(),
+            data,
+        }
+    }
+
+    fn positives(fold: &Fold, y: &NamedArray<f64>) -> usize {
+        fold.test.iter().filter(|i| y.data[**i] == 1.0).count()
+    }
+
+    #[rstest]
+    fn test_stratified_kfold(imbalanced_target: NamedArray<f64>) {
+        let folds = StratifiedKFold::new(3)
+            .unwrap()
+            .split(&imbalanced_target)
+            .unwrap();
+        assert_eq!(folds.len(), 3);
+        for fold in folds.iter() {
+            assert_eq!(fold.test.len(), 4);
+            assert_eq!(positives(fold, &imbalanced_target), 1);
+        }
+        assert_eq!(folds[0].test, vec![0, 1, 4, 8]);
+    }
+
+    #[rstest]
+    fn test_stratified_kfold_shuffle(imbalanced_target: NamedArray<f64>) {
+        let splitter = StratifiedKFold::new(3).unwrap().with_shuffle(Some(1));
+        let folds = splitter.split(&imbalanced_target).unwrap();
+        assert_eq!(folds, splitter.split(&imbalanced_target).unwrap());
+
+        let mut seen: Vec<usize> = folds.iter().flat_map(|f| f.test.clone()).collect();
+        seen.sort();
+        assert_eq!(seen, (0..12).collect::<Vec<usize>>());
+        for fold in folds.iter() {
+            assert_eq!(positives(fold, &imbalanced_target), 1);
+        }
+    }
+
+    #[rstest]
+    fn test_stratified_kfold_class_too_small(imbalanced_target: NamedArray<f64>) {
+        assert!(StratifiedKFold::new(4)
+            .unwrap()
+            .split(&imbalanced_target)
+            .is_err());
+        assert!(StratifiedKFold::new(1).is_err());
+    }
+}
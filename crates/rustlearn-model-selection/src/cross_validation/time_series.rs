@@ -0,0 +1,201 @@
+use crate::cross_validation::{assert_min_n_splits, CrossValidator, Result};
+use crate::types::Fold;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WindowType {
+    Expanding,
+    Sliding,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TimeSeriesSplit {
+    pub n_splits: usize,
+    pub test_size: Option<usize>,
+    pub gap: usize,
+    pub max_train_size: Option<usize>,
+    pub window: WindowType,
+}
+
+impl TimeSeriesSplit {
+    pub fn new(n_splits: usize) -> Result<Self> {
+        assert_min_n_splits(n_splits)?;
+        Ok(Self {
+            n_splits,
+            test_size: None,
+            gap: 0,
+            max_train_size: None,
+            window: WindowType::Expanding,
+        })
+    }
+
+    pub fn with_test_size(mut self, test_size: usize) -> Result<Self> {
+        if test_size == 0 {
+            return Err(ValidationError(ErrString::from(
+                "test_size must be at least 1",
+            )));
+        }
+        self.test_size = Some(test_size);
+        Ok(self)
+    }
+
+    // rows dropped between the end of training and the start of testing
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn with_max_train_size(mut self, max_train_size: usize) -> Result<Self> {
+        if max_train_size == 0 {
+            return Err(ValidationError(ErrString::from(
+                "max_train_size must be at least 1",
+            )));
+        }
+        self.max_train_size = Some(max_train_size);
+        Ok(self)
+    }
+
+    pub fn with_window(mut self, window: WindowType) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+impl CrossValidator for TimeSeriesSplit {
+    fn split<T>(&self, y: &NamedArray<T>) -> Result<Vec<Fold>>
+    where
+        T: ToPrimitive,
+    {
+        let n_rows = y.data.len();
+        let test_size = self.test_size.unwrap_or(n_rows / (self.n_splits + 1));
+        if test_size == 0 || self.n_splits * test_size > n_rows {
+            return Err(ValidationError(ErrString::from(format!(
+                "cannot fit {} test folds into {n_rows} rows",
+                self.n_splits
+            ))));
+        }
+
+        let first_test_start = n_rows - self.n_splits * test_size;
+        if first_test_start <= self.gap {
+            return Err(ValidationError(ErrString::from(
+                "no rows left for training the first fold once the gap is removed",
+            )));
+        }
+
+        // a sliding window keeps the size of the first training window unless
+        // told otherwise
+        let first_train_end = first_test_start - self.gap;
+        let window_size = match (self.window, self.max_train_size) {
+            (_, Some(max_train_size)) => max_train_size,
+            (WindowType::Sliding, None) => first_train_end,
+            (WindowType::Expanding, None) => n_rows,
+        };
+
+        Ok((0..self.n_splits)
+            .map(|k| {
+                let test_start = first_test_start + k * test_size;
+                let train_end = test_start - self.gap;
+                Fold {
+                    train: (train_end.saturating_sub(window_size)..train_end).collect(),
+                    test: (test_start..test_start + test_size).collect(),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "sales".to_string(),
+            data: (0..10).map(|i| i as f64).collect(),
+        }
+    }
+
+    fn range(start: usize, end: usize) -> Vec<usize> {
+        (start..end).collect()
+    }
+
+    #[rstest]
+    fn test_expanding(target_named_array: NamedArray<f64>) {
+        let folds = TimeSeriesSplit::new(3)
+            .unwrap()
+            .split(&target_named_array)
+            .unwrap();
+        assert_eq!(folds.len(), 3);
+        assert_eq!(folds[0].train, range(0, 4));
+        assert_eq!(folds[0].test, range(4, 6));
+        assert_eq!(folds[1].train, range(0, 6));
+        assert_eq!(folds[1].test, range(6, 8));
+        assert_eq!(folds[2].train, range(0, 8));
+        assert_eq!(folds[2].test, range(8, 10));
+    }
+
+    #[rstest]
+    fn test_sliding(target_named_array: NamedArray<f64>) {
+        let folds = TimeSeriesSplit::new(3)
+            .unwrap()
+            .with_window(WindowType::Sliding)
+            .split(&target_named_array)
+            .unwrap();
+        assert_eq!(folds[0].train, range(0, 4));
+        assert_eq!(folds[1].train, range(2, 6));
+        assert_eq!(folds[2].train, range(4, 8));
+    }
+
+    #[rstest]
+    fn test_gap_and_max_train_size(target_named_array: NamedArray<f64>) {
+        let folds = TimeSeriesSplit::new(2)
+            .unwrap()
+            .with_test_size(2)
+            .unwrap()
+            .with_gap(1)
+            .with_max_train_size(3)
+            .unwrap()
+            .split(&target_named_array)
+            .unwrap();
+        assert_eq!(folds[0].train, range(2, 5));
+        assert_eq!(folds[0].test, range(6, 8));
+        assert_eq!(folds[1].train, range(4, 7));
+        assert_eq!(folds[1].test, range(8, 10));
+    }
+
+    #[rstest]
+    fn test_no_future_leakage(target_named_array: NamedArray<f64>) {
+        let folds = TimeSeriesSplit::new(4)
+            .unwrap()
+            .with_gap(1)
+            .split(&target_named_array)
+            .unwrap();
+        for fold in folds.iter() {
+            assert!(fold.train.iter().max().unwrap() + 1 < *fold.test.iter().min().unwrap());
+        }
+    }
+
+    #[rstest]
+    fn test_errors(target_named_array: NamedArray<f64>) {
+        assert!(TimeSeriesSplit::new(1).is_err());
+        assert!(TimeSeriesSplit::new(2).unwrap().with_test_size(0).is_err());
+        assert!(TimeSeriesSplit::new(2)
+            .unwrap()
+            .with_max_train_size(0)
+            .is_err());
+        assert!(TimeSeriesSplit::new(10)
+            .unwrap()
+            .split(&target_named_array)
+            .is_err());
+        assert!(TimeSeriesSplit::new(3)
+            .unwrap()
+            .with_gap(4)
+            .split(&target_named_array)
+            .is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use crate::cross_validation::{materialize_folds, CrossValidator};
+use crate::estimator::Estimator;
+use crate::rng::seeded_rng;
+use crate::scoring::Scorer;
+use crate::types::{take_rows, TrainTestSplitReturn};
+use num::ToPrimitive;
+use rand::seq::SliceRandom;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+// scores are indexed [point on the curve][fold]
+#[derive(Debug, PartialEq, Clone)]
+pub struct LearningCurveReturn {
+    pub train_sizes: Vec<usize>,
+    pub train_scores: Vec<Vec<f64>>,
+    pub test_scores: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationCurveReturn {
+    pub param_range: Vec<f64>,
+    pub train_scores: Vec<Vec<f64>>,
+    pub test_scores: Vec<Vec<f64>>,
+}
+
+fn fit_and_score<E>(
+    estimator: &E,
+    split: &TrainTestSplitReturn,
+    scorer: &Scorer,
+) -> Result<(f64, f64)>
+where
+    E: Estimator,
+{
+    let mut model = estimator.clone();
+    model.fit(&split.x_train, &split.y_train)?;
+    let train_score = scorer.score(&split.y_train, &model.predict(&split.x_train)?)?;
+    let test_score = scorer.score(&split.y_test, &model.predict(&split.x_test)?)?;
+    Ok((train_score, test_score))
+}
+
+// keeps the given rows of the training partition, the test side untouched
+fn shrink_train(split: &TrainTestSplitReturn, rows: &[usize]) -> TrainTestSplitReturn {
+    TrainTestSplitReturn {
+        x_train: split.x_train.iter().map(|nm| take_rows(nm, rows)).collect(),
+        y_train: take_rows(&split.y_train, rows),
+        x_test: split.x_test.clone(),
+        y_test: split.y_test.clone(),
+    }
+}
+
+pub fn learning_curve<E, S, T>(
+    estimator: &E,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    train_sizes: &[f64],
+    scorer: &Scorer,
+    random_state: Option<u64>,
+) -> Result<LearningCurveReturn>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive,
+{
+    if train_sizes.is_empty() {
+        return Err(ValidationError(ErrString::from("no train sizes provided")));
+    }
+    if train_sizes.iter().any(|size| *size <= 0.0 || *size > 1.0) {
+        return Err(ValidationError(ErrString::from(
+            "train sizes must be proportions between 0 and 1",
+        )));
+    }
+    let splits = materialize_folds(x, y, &splitter.split(y)?)?;
+
+    // sizes are taken from the smallest training fold so every fold can
+    // provide them
+    let n_train = splits.iter().map(|s| s.y_train.data.len()).min().unwrap();
+    let absolute: Vec<usize> = train_sizes
+        .iter()
+        .map(|size| ((size * n_train as f64).ceil() as usize).max(1))
+        .collect();
+
+    // each fold shuffles its training rows once, so the subsets are random
+    // but nested, and keep their original order
+    let mut rng = seeded_rng(random_state);
+    let orders: Vec<Vec<usize>> = splits
+        .iter()
+        .map(|split| {
+            let mut order: Vec<usize> = (0..split.y_train.data.len()).collect();
+            order.shuffle(&mut rng);
+            order
+        })
+        .collect();
+
+    let mut train_scores: Vec<Vec<f64>> = Vec::new();
+    let mut test_scores: Vec<Vec<f64>> = Vec::new();
+    for n_rows in absolute.iter() {
+        let mut train_row: Vec<f64> = Vec::new();
+        let mut test_row: Vec<f64> = Vec::new();
+        for (split, order) in splits.iter().zip(orders.iter()) {
+            let mut rows = order[0..*n_rows].to_vec();
+            rows.sort();
+            let (train, test) = fit_and_score(estimator, &shrink_train(split, &rows), scorer)?;
+            train_row.push(train);
+            test_row.push(test);
+        }
+        train_scores.push(train_row);
+        test_scores.push(test_row);
+    }
+
+    Ok(LearningCurveReturn {
+        train_sizes: absolute,
+        train_scores,
+        test_scores,
+    })
+}
+
+pub fn validation_curve<E, S, T>(
+    estimator: &E,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    param_name: &str,
+    param_range: &[f64],
+    scorer: &Scorer,
+) -> Result<ValidationCurveReturn>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive,
+{
+    if param_range.is_empty() {
+        return Err(ValidationError(ErrString::from(
+            "no parameter values provided",
+        )));
+    }
+    let splits = materialize_folds(x, y, &splitter.split(y)?)?;
+
+    let mut train_scores: Vec<Vec<f64>> = Vec::new();
+    let mut test_scores: Vec<Vec<f64>> = Vec::new();
+    for value in param_range.iter() {
+        let mut model = estimator.clone();
+        model.set_param(param_name, *value)?;
+        let mut train_row: Vec<f64> = Vec::new();
+        let mut test_row: Vec<f64> = Vec::new();
+        for split in splits.iter() {
+            let (train, test) = fit_and_score(&model, split, scorer)?;
+            train_row.push(train);
+            test_row.push(test);
+        }
+        train_scores.push(train_row);
+        test_scores.push(test_row);
+    }
+
+    Ok(ValidationCurveReturn {
+        param_range: param_range.to_vec(),
+        train_scores,
+        test_scores,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_validate::tests::MeanEstimator;
+    use crate::cross_validation::kfold::KFold;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }
+    }
+
+    #[rstest]
+    fn test_learning_curve(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let res = learning_curve(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            &[0.5, 1.0],
+            &Scorer::MeanSquaredError,
+            Some(0),
+        )
+        .unwrap();
+
+        assert_eq!(res.train_sizes, vec![1, 2]);
+        assert_eq!(res.train_scores, vec![vec![0.0, 0.0], vec![0.25, 0.25]]);
+        // a single training row predicts itself, scoring 2.5 on the test fold
+        // when it is the row nearer to it and 6.5 otherwise
+        for score in res.test_scores[0].iter() {
+            assert!(*score == 2.5 || *score == 6.5);
+        }
+        assert_eq!(res.test_scores[1], vec![4.25, 4.25]);
+    }
+
+    #[rstest]
+    fn test_learning_curve_subsets(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let curve = |random_state| {
+            learning_curve(
+                &MeanEstimator::default(),
+                &input_named_array,
+                &target_named_array,
+                &KFold::new(2).unwrap(),
+                &[0.5],
+                &Scorer::MeanSquaredError,
+                random_state,
+            )
+            .unwrap()
+            .test_scores
+        };
+        assert_eq!(curve(Some(3)), curve(Some(3)));
+        // the subset is not always the first row of each training fold
+        assert!((0..20).any(|seed| curve(Some(seed)) != vec![vec![2.5, 6.5]]));
+    }
+
+    #[rstest]
+    fn test_learning_curve_errors(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        for sizes in [vec![], vec![0.0], vec![1.5]] {
+            assert!(learning_curve(
+                &MeanEstimator::default(),
+                &input_named_array,
+                &target_named_array,
+                &KFold::new(2).unwrap(),
+                &sizes,
+                &Scorer::R2,
+                None,
+            )
+            .is_err());
+        }
+    }
+
+    #[rstest]
+    fn test_validation_curve(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let res = validation_curve(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            "shrinkage",
+            &[0.0, 0.5],
+            &Scorer::MeanSquaredError,
+        )
+        .unwrap();
+
+        assert_eq!(res.param_range, vec![0.0, 0.5]);
+        assert_eq!(res.train_scores[0], vec![0.25, 0.25]);
+        assert_eq!(res.test_scores[0], vec![4.25, 4.25]);
+        assert_eq!(res.train_scores[1][0], 3.3125);
+        assert_eq!(res.test_scores[1], vec![0.3125, 7.8125]);
+    }
+
+    #[rstest]
+    fn test_validation_curve_errors(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        assert!(validation_curve(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            "shrinkage",
+            &[],
+            &Scorer::R2,
+        )
+        .is_err());
+        assert!(validation_curve(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            "alpha",
+            &[1.0],
+            &Scorer::R2,
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_registered_scorer(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let res = validation_curve(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            "shrinkage",
+            &[0.0, 0.5],
+            &Scorer::from_name("neg_mean_squared_error").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.test_scores[1], vec![-0.3125, -7.8125]);
+
+        let res = learning_curve(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(2).unwrap(),
+            &[1.0],
+            &Scorer::from_name("neg_mean_squared_error").unwrap(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.test_scores, vec![vec![-4.25, -4.25]]);
+    }
+}
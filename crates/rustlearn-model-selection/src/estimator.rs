@@ -0,0 +1,21 @@
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+// anything that can be refit on a fold and asked for predictions; estimators
+// are cloned before fitting so every fold starts from the same configuration
+pub trait Estimator: Clone {
+    fn fit(&mut self, x: &[NamedArray<f64>], y: &NamedArray<f64>) -> Result<()>;
+
+    fn predict(&self, x: &[NamedArray<f64>]) -> Result<NamedArray<f64>>;
+
+    // hyperparameters are set by name so searches can drive any estimator;
+    // one without any, like LinearRegressionEstimator, rejects every name
+    fn set_param(&mut self, name: &str, _value: f64) -> Result<()> {
+        Err(ValidationError(ErrString::from(format!(
+            "estimator has no parameter named {name}"
+        ))))
+    }
+}
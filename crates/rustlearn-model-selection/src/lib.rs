@@ -1,2 +1,11 @@
+pub mod bootstrap;
+pub mod cross_validate;
+pub mod cross_validation;
+pub mod curves;
+pub mod estimator;
+pub mod permutation;
+pub mod rng;
+pub mod scoring;
+pub mod search;
 pub mod train_test_split;
 pub mod types;
@@ -0,0 +1,218 @@
+use crate::cross_validate::cross_val_score;
+use crate::cross_validation::CrossValidator;
+use crate::estimator::Estimator;
+use crate::rng::seeded_rng;
+use crate::scoring::Scorer;
+use num::ToPrimitive;
+use rand::seq::SliceRandom;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PermutationTestReturn {
+    pub score: f64,
+    pub permutation_scores: Vec<f64>,
+    pub pvalue: f64,
+}
+
+fn mean(scores: &[f64]) -> f64 {
+    scores.iter().sum::<f64>() / scores.len() as f64
+}
+
+pub fn permutation_test_score<E, S, T>(
+    estimator: &E,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    scorer: &Scorer,
+    n_permutations: usize,
+    random_state: Option<u64>,
+) -> Result<PermutationTestReturn>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive + Copy,
+{
+    if n_permutations == 0 {
+        return Err(ValidationError(ErrString::from(
+            "n_permutations must be at least 1",
+        )));
+    }
+    let score = mean(&cross_val_score(estimator, x, y, splitter, scorer)?);
+
+    let mut rng = seeded_rng(random_state);
+    let mut permuted = y.clone();
+    let mut permutation_scores: Vec<f64> = Vec::new();
+    for _ in 0..n_permutations {
+        permuted.data.shuffle(&mut rng);
+        permutation_scores.push(mean(&cross_val_score(
+            estimator, x, &permuted, splitter, scorer,
+        )?));
+    }
+
+    // the observed score counts as one of the permutations, so the p-value
+    // is never exactly zero
+    let as_good = permutation_scores
+        .iter()
+        .filter(|s| {
+            if scorer.greater_is_better() {
+                **s >= score
+            } else {
+                **s <= score
+            }
+        })
+        .count();
+    Ok(PermutationTestReturn {
+        score,
+        pvalue: (as_good + 1) as f64 / (n_permutations + 1) as f64,
+        permutation_scores,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_validate::tests::MeanEstimator;
+    use crate::cross_validation::kfold::KFold;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    // least squares on the first column
+    #[derive(Debug, Clone, Default)]
+    struct SlopeEstimator {
+        intercept: f64,
+        slope: f64,
+    }
+
+    impl Estimator for SlopeEstimator {
+        fn fit(&mut self, x: &[NamedArray<f64>], y: &NamedArray<f64>) -> Result<()> {
+            let (m_x, m_y) = (x[0].mean()?, y.mean()?);
+            let mut ss_xy = 0.0;
+            let mut ss_xx = 0.0;
+            for (a, b) in x[0].data.iter().zip(y.data.iter()) {
+                ss_xy += (a - m_x) * (b - m_y);
+                ss_xx += (a - m_x).powi(2);
+            }
+            self.slope = ss_xy / ss_xx;
+            self.intercept = m_y - self.slope * m_x;
+            Ok(())
+        }
+
+        fn predict(&self, x: &[NamedArray<f64>]) -> Result<NamedArray<f64>> {
+            Ok(x[0].map("predictions", |v| self.intercept + self.slope * v))
+        }
+    }
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: (0..20).map(|i| i as f64).collect(),
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: (0..20)
+                .map(|i| 2.0 * i as f64 + [0.5, -0.5, 1.0, -1.0][i % 4])
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    fn test_significant_model(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let splitter = KFold::new(4).unwrap().with_shuffle(Some(0));
+        let res = permutation_test_score(
+            &SlopeEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &splitter,
+            &Scorer::R2,
+            30,
+            Some(3),
+        )
+        .unwrap();
+
+        assert!(res.score > 0.9);
+        assert_eq!(res.permutation_scores.len(), 30);
+        assert!(res.permutation_scores.iter().all(|s| *s < res.score));
+        assert_float_relative_eq!(res.pvalue, 1.0 / 31.0, 0.001);
+
+        let again = permutation_test_score(
+            &SlopeEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &splitter,
+            &Scorer::R2,
+            30,
+            Some(3),
+        )
+        .unwrap();
+        assert_eq!(res, again);
+    }
+
+    #[rstest]
+    fn test_uninformative_model(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        // the training mean ignores x, so pairing it with the original
+        // target is no better than pairing it with a shuffled one
+        let res = permutation_test_score(
+            &MeanEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(4).unwrap(),
+            &Scorer::MeanSquaredError,
+            20,
+            Some(3),
+        )
+        .unwrap();
+        assert!(res.pvalue > 0.05);
+    }
+
+    #[rstest]
+    fn test_no_permutations(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        assert!(permutation_test_score(
+            &SlopeEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(4).unwrap(),
+            &Scorer::R2,
+            0,
+            None,
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn test_registered_scorer(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        // a negated loss is better when larger, like r2
+        let res = permutation_test_score(
+            &SlopeEstimator::default(),
+            &input_named_array,
+            &target_named_array,
+            &KFold::new(4).unwrap().with_shuffle(Some(0)),
+            &Scorer::from_name("neg_mean_squared_error").unwrap(),
+            30,
+            Some(3),
+        )
+        .unwrap();
+        assert!(res.score <= 0.0);
+        assert_float_relative_eq!(res.pvalue, 1.0 / 31.0, 0.001);
+    }
+}
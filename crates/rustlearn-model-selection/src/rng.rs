@@ -0,0 +1,9 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub fn seeded_rng(random_state: Option<u64>) -> ChaCha8Rng {
+    match random_state {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    }
+}
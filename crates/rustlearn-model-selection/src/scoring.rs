@@ -0,0 +1,132 @@
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+use rustlearn_metrics::mean_absolute_error::mean_absolute_error;
+use rustlearn_metrics::mean_squared_error::mean_squared_error;
+use rustlearn_metrics::r_2::r_2;
+use rustlearn_metrics::scorer::{RegisteredScorer, ScorerRegistry};
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+// Registered takes any scorer from a ScorerRegistry, built-in or custom. It
+// reports the registry's score, which is negated for losses, so unlike the
+// other variants a larger value is always better. A scorer that needs
+// probabilities is given the estimator's predictions as its scores.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Scorer {
+    MeanSquaredError,
+    MeanAbsoluteError,
+    R2,
+    Registered(RegisteredScorer),
+}
+
+impl Scorer {
+    // a scorer from the built-in registry, e.g. "roc_auc"
+    pub fn from_name(name: &str) -> Result<Self> {
+        Scorer::from_registry(&ScorerRegistry::new(), name)
+    }
+
+    pub fn from_registry(registry: &ScorerRegistry, name: &str) -> Result<Self> {
+        Ok(Scorer::Registered(registry.get(name)?.clone()))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Scorer::MeanSquaredError => "mean_squared_error",
+            Scorer::MeanAbsoluteError => "mean_absolute_error",
+            Scorer::R2 => "r2",
+            Scorer::Registered(scorer) => &scorer.name,
+        }
+    }
+
+    pub fn greater_is_better(&self) -> bool {
+        matches!(self, Scorer::R2 | Scorer::Registered(_))
+    }
+
+    pub fn score(&self, y_true: &NamedArray<f64>, y_pred: &NamedArray<f64>) -> Result<f64> {
+        match self {
+            Scorer::MeanSquaredError => mean_squared_error(y_true.clone(), y_pred.clone(), None),
+            Scorer::MeanAbsoluteError => mean_absolute_error(y_true.clone(), y_pred.clone(), None),
+            Scorer::R2 => r_2(y_true.clone(), y_pred.clone(), None),
+            Scorer::Registered(scorer) => scorer.score(y_true, y_pred),
+        }
+    }
+}
+
+impl From<RegisteredScorer> for Scorer {
+    fn from(scorer: RegisteredScorer) -> Self {
+        Scorer::Registered(scorer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn y_true() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0],
+        }
+    }
+
+    #[fixture]
+    fn y_pred() -> NamedArray<f64> {
+        NamedArray {
+            name: "predictions".to_string(),
+            data: vec![1.0, 2.0, 5.0],
+        }
+    }
+
+    #[rstest]
+    fn test_scorers(y_true: NamedArray<f64>, y_pred: NamedArray<f64>) {
+        assert_float_relative_eq!(
+            Scorer::MeanSquaredError.score(&y_true, &y_pred).unwrap(),
+            4.0 / 3.0,
+            0.001
+        );
+        assert_float_relative_eq!(
+            Scorer::MeanAbsoluteError.score(&y_true, &y_pred).unwrap(),
+            2.0 / 3.0,
+            0.001
+        );
+        assert_float_relative_eq!(Scorer::R2.score(&y_true, &y_pred).unwrap(), -1.0, 0.001);
+        assert!(Scorer::R2.greater_is_better());
+        assert!(!Scorer::MeanSquaredError.greater_is_better());
+        assert_eq!(Scorer::MeanAbsoluteError.name(), "mean_absolute_error");
+    }
+
+    #[rstest]
+    fn test_registered_scorers(y_true: NamedArray<f64>, y_pred: NamedArray<f64>) {
+        let scorer = Scorer::from_name("neg_mean_squared_error").unwrap();
+        assert_eq!(scorer.name(), "neg_mean_squared_error");
+        assert!(scorer.greater_is_better());
+        assert_float_relative_eq!(scorer.score(&y_true, &y_pred).unwrap(), -4.0 / 3.0, 0.001);
+        assert!(Scorer::from_name("roc_auc").is_ok());
+        assert!(Scorer::from_name("not_a_scorer").is_err());
+
+        let mut registry = ScorerRegistry::empty();
+        registry
+            .register(RegisteredScorer::new(
+                "worst_error",
+                false,
+                false,
+                |t, p| {
+                    Ok(t.data
+                        .iter()
+                        .zip(p.data.iter())
+                        .map(|(t, p)| (t - p).abs())
+                        .fold(0.0, f64::max))
+                },
+            ))
+            .unwrap();
+        let custom = Scorer::from_registry(&registry, "worst_error").unwrap();
+        assert_float_relative_eq!(custom.score(&y_true, &y_pred).unwrap(), -2.0, 0.001);
+        assert_eq!(
+            Scorer::from(registry.get("worst_error").unwrap().clone()),
+            custom
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use rand::Rng;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+use crate::search::Result;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Distribution {
+    Uniform { low: f64, high: f64 },
+    LogUniform { low: f64, high: f64 },
+    IntUniform { low: i64, high: i64 },
+    Choice(Vec<f64>),
+}
+
+impl Distribution {
+    pub fn validate(&self) -> Result<()> {
+        let valid = match self {
+            Distribution::Uniform { low, high } => low < high,
+            Distribution::LogUniform { low, high } => *low > 0.0 && low < high,
+            Distribution::IntUniform { low, high } => low <= high,
+            Distribution::Choice(values) => !values.is_empty(),
+        };
+        if !valid {
+            return Err(ValidationError(ErrString::from(format!(
+                "invalid distribution {self:?}"
+            ))));
+        }
+        Ok(())
+    }
+
+    // uniform ranges are half open, integer ranges include both ends
+    pub fn sample<R>(&self, rng: &mut R) -> f64
+    where
+        R: Rng,
+    {
+        match self {
+            Distribution::Uniform { low, high } => rng.gen_range(*low..*high),
+            Distribution::LogUniform { low, high } => rng.gen_range(low.ln()..high.ln()).exp(),
+            Distribution::IntUniform { low, high } => rng.gen_range(*low..=*high) as f64,
+            Distribution::Choice(values) => values[rng.gen_range(0..values.len())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::seeded_rng;
+    use rstest::*;
+
+    #[rstest]
+    fn test_validate() {
+        assert!(Distribution::Uniform {
+            low: 1.0,
+            high: 0.0
+        }
+        .validate()
+        .is_err());
+        assert!(Distribution::LogUniform {
+            low: 0.0,
+            high: 1.0
+        }
+        .validate()
+        .is_err());
+        assert!(Distribution::IntUniform { low: 3, high: 3 }
+            .validate()
+            .is_ok());
+        assert!(Distribution::Choice(vec![]).validate().is_err());
+    }
+
+    #[rstest]
+    fn test_sample_bounds() {
+        let mut rng = seeded_rng(Some(0));
+        for _ in 0..100 {
+            let v = Distribution::Uniform {
+                low: -1.0,
+                high: 1.0,
+            }
+            .sample(&mut rng);
+            assert!((-1.0..1.0).contains(&v));
+
+            let v = Distribution::LogUniform {
+                low: 1e-4,
+                high: 10.0,
+            }
+            .sample(&mut rng);
+            assert!((1e-4..10.0).contains(&v));
+
+            let v = Distribution::IntUniform { low: 1, high: 3 }.sample(&mut rng);
+            assert!([1.0, 2.0, 3.0].contains(&v));
+
+            let v = Distribution::Choice(vec![0.1, 0.2]).sample(&mut rng);
+            assert!([0.1, 0.2].contains(&v));
+        }
+    }
+}
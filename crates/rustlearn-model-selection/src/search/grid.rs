@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use crate::cross_validation::CrossValidator;
+use crate::estimator::Estimator;
+use crate::scoring::Scorer;
+use crate::search::{evaluate_candidates, refit_best, Result, SearchReturn};
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GridSearchCV<E, S> {
+    pub estimator: E,
+    pub param_grid: HashMap<String, Vec<f64>>,
+    pub splitter: S,
+    pub scorer: Scorer,
+}
+
+pub fn assert_logical_param_grid(param_grid: &HashMap<String, Vec<f64>>) -> Result<()> {
+    if param_grid.is_empty() {
+        return Err(ValidationError(ErrString::from("param_grid is empty")));
+    }
+    for (name, values) in param_grid.iter() {
+        if values.is_empty() {
+            return Err(ValidationError(ErrString::from(format!(
+                "no values provided for parameter {name}"
+            ))));
+        }
+    }
+    Ok(())
+}
+
+// the cartesian product of the grid, walking parameters in name order so the
+// candidates come out the same way every time
+pub fn grid_candidates(param_grid: &HashMap<String, Vec<f64>>) -> Vec<HashMap<String, f64>> {
+    let mut names: Vec<&String> = param_grid.keys().collect();
+    names.sort();
+
+    let mut candidates: Vec<HashMap<String, f64>> = vec![HashMap::new()];
+    for name in names {
+        candidates = candidates
+            .iter()
+            .flat_map(|candidate| {
+                param_grid[name].iter().map(move |value| {
+                    let mut next = candidate.clone();
+                    next.insert(name.clone(), *value);
+                    next
+                })
+            })
+            .collect();
+    }
+    candidates
+}
+
+impl<E, S> GridSearchCV<E, S>
+where
+    E: Estimator,
+    S: CrossValidator,
+{
+    pub fn new(
+        estimator: E,
+        param_grid: HashMap<String, Vec<f64>>,
+        splitter: S,
+        scorer: Scorer,
+    ) -> Result<Self> {
+        assert_logical_param_grid(&param_grid)?;
+        Ok(Self {
+            estimator,
+            param_grid,
+            splitter,
+            scorer,
+        })
+    }
+
+    pub fn fit<T>(&self, x: &[NamedArray<T>], y: &NamedArray<T>) -> Result<SearchReturn<E>>
+    where
+        T: ToPrimitive,
+    {
+        let results = evaluate_candidates(
+            &self.estimator,
+            grid_candidates(&self.param_grid),
+            x,
+            y,
+            &self.splitter,
+            &self.scorer,
+        )?;
+        refit_best(&self.estimator, results, x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_validate::tests::MeanEstimator;
+    use crate::cross_validation::kfold::KFold;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }
+    }
+
+    #[rstest]
+    fn test_grid_candidates() {
+        let grid = HashMap::from([
+            ("b".to_string(), vec![3.0, 4.0, 5.0]),
+            ("a".to_string(), vec![1.0, 2.0]),
+        ]);
+        let candidates = grid_candidates(&grid);
+        assert_eq!(candidates.len(), 6);
+        assert_eq!(candidates[0]["a"], 1.0);
+        assert_eq!(candidates[0]["b"], 3.0);
+        assert_eq!(candidates[1]["b"], 4.0);
+        assert_eq!(candidates[5]["a"], 2.0);
+        assert_eq!(candidates[5]["b"], 5.0);
+    }
+
+    #[rstest]
+    fn test_grid_search(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let grid = HashMap::from([("shrinkage".to_string(), vec![0.0, 0.5, -0.5])]);
+        let search = GridSearchCV::new(
+            MeanEstimator::default(),
+            grid,
+            KFold::new(2).unwrap(),
+            Scorer::MeanSquaredError,
+        )
+        .unwrap();
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+
+        assert_eq!(res.results.len(), 3);
+        assert_float_relative_eq!(res.results[0].mean_test_score, 4.25, 0.001);
+        assert_float_relative_eq!(res.results[1].mean_test_score, 4.0625, 0.001);
+        assert_float_relative_eq!(res.results[1].std_test_score, 3.75, 0.001);
+        assert_float_relative_eq!(res.results[2].mean_test_score, 8.0625, 0.001);
+        let ranks: Vec<usize> = res.results.iter().map(|r| r.rank_test_score).collect();
+        assert_eq!(ranks, vec![2, 1, 3]);
+
+        assert_eq!(res.best_index, 1);
+        assert_eq!(res.best_params["shrinkage"], 0.5);
+        assert_float_relative_eq!(res.best_score, 4.0625, 0.001);
+        // refit on all four rows
+        assert_float_relative_eq!(res.best_estimator.mean, 2.5, 0.001);
+        assert_float_relative_eq!(res.best_estimator.shrinkage, 0.5, 0.001);
+    }
+
+    #[rstest]
+    fn test_grid_search_errors(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        assert!(GridSearchCV::new(
+            MeanEstimator::default(),
+            HashMap::new(),
+            KFold::new(2).unwrap(),
+            Scorer::R2,
+        )
+        .is_err());
+        assert!(GridSearchCV::new(
+            MeanEstimator::default(),
+            HashMap::from([("shrinkage".to_string(), vec![])]),
+            KFold::new(2).unwrap(),
+            Scorer::R2,
+        )
+        .is_err());
+
+        let unknown = GridSearchCV::new(
+            MeanEstimator::default(),
+            HashMap::from([("alpha".to_string(), vec![1.0])]),
+            KFold::new(2).unwrap(),
+            Scorer::R2,
+        )
+        .unwrap();
+        assert!(unknown
+            .fit(&input_named_array, &target_named_array)
+            .is_err());
+    }
+
+    #[rstest]
+    fn test_grid_search_registered_scorer(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let grid = HashMap::from([("shrinkage".to_string(), vec![0.0, 0.5, -0.5])]);
+        let search = GridSearchCV::new(
+            MeanEstimator::default(),
+            grid,
+            KFold::new(2).unwrap(),
+            Scorer::from_name("neg_mean_squared_error").unwrap(),
+        )
+        .unwrap();
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+
+        // the same ranking as the raw loss, with the scores negated
+        let ranks: Vec<usize> = res.results.iter().map(|r| r.rank_test_score).collect();
+        assert_eq!(ranks, vec![2, 1, 3]);
+        assert_eq!(res.best_index, 1);
+        assert_float_relative_eq!(res.best_score, -4.0625, 0.001);
+    }
+}
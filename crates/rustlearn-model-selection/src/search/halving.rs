@@ -0,0 +1,638 @@
+use std::collections::HashMap;
+
+use crate::cross_validation::CrossValidator;
+use crate::estimator::Estimator;
+use crate::rng::seeded_rng;
+use crate::scoring::Scorer;
+use crate::search::distributions::Distribution;
+use crate::search::grid::{assert_logical_param_grid, grid_candidates};
+use crate::search::randomized::{assert_logical_param_distributions, sample_candidates};
+use crate::search::{evaluate_candidates, refit_best, CandidateResult, Result, SearchReturn};
+use crate::types::take_rows;
+use num::ToPrimitive;
+use rand::seq::SliceRandom;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+// what gets handed out in growing amounts each round: a subsample of the
+// rows, or an estimator parameter such as an iteration count
+#[derive(Debug, PartialEq, Clone)]
+pub enum Resource {
+    Rows,
+    Param { name: String, max: usize },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HalvingSchedule {
+    pub factor: usize,
+    pub resource: Resource,
+    pub min_resources: Option<usize>,
+    pub random_state: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HalvingRound {
+    pub n_resources: usize,
+    pub results: Vec<CandidateResult>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HalvingSearchReturn<E> {
+    pub rounds: Vec<HalvingRound>,
+    pub best_params: HashMap<String, f64>,
+    pub best_score: f64,
+    pub best_estimator: E,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HalvingGridSearch<E, S> {
+    pub estimator: E,
+    pub param_grid: HashMap<String, Vec<f64>>,
+    pub splitter: S,
+    pub scorer: Scorer,
+    pub schedule: HalvingSchedule,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HalvingRandomSearch<E, S> {
+    pub estimator: E,
+    pub param_distributions: HashMap<String, Distribution>,
+    pub n_candidates: usize,
+    pub splitter: S,
+    pub scorer: Scorer,
+    pub schedule: HalvingSchedule,
+}
+
+impl HalvingSchedule {
+    // keep a third of the candidates each round, growing the rows threefold
+    pub fn new(random_state: Option<u64>) -> Self {
+        Self {
+            factor: 3,
+            resource: Resource::Rows,
+            min_resources: None,
+            random_state,
+        }
+    }
+}
+
+fn assert_logical_factor(factor: usize) -> Result<()> {
+    if factor < 2 {
+        return Err(ValidationError(ErrString::from(
+            "factor must be at least 2",
+        )));
+    }
+    Ok(())
+}
+
+fn assert_logical_resource(resource: &Resource, min_resources: Option<usize>) -> Result<()> {
+    if min_resources == Some(0) {
+        return Err(ValidationError(ErrString::from(
+            "min_resources must be at least 1",
+        )));
+    }
+    if let Resource::Param { max, .. } = resource {
+        if *max == 0 || min_resources.is_some_and(|min| min > *max) {
+            return Err(ValidationError(ErrString::from(
+                "resource maximum must be at least min_resources",
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub fn successive_halving<E, S, T>(
+    estimator: &E,
+    mut candidates: Vec<HashMap<String, f64>>,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    scorer: &Scorer,
+    schedule: &HalvingSchedule,
+) -> Result<HalvingSearchReturn<E>>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive,
+{
+    let factor = schedule.factor;
+    let resource = &schedule.resource;
+    let max_resources = match resource {
+        Resource::Rows => y.data.len(),
+        Resource::Param { max, .. } => *max,
+    };
+    // row subsamples are nested and keep their original order
+    let mut order: Vec<usize> = (0..y.data.len()).collect();
+    order.shuffle(&mut seeded_rng(schedule.random_state));
+    let subsample = |n_rows: usize| {
+        let mut rows = order[0..n_rows].to_vec();
+        rows.sort();
+        rows
+    };
+    let splittable = |n_rows: usize| splitter.split(&take_rows(y, &subsample(n_rows))).is_ok();
+
+    // by default start small enough that the last round runs on the full
+    // budget, but never on fewer rows than the splitter can cross validate
+    let min_resources = match schedule.min_resources {
+        Some(min_resources) => min_resources,
+        None => {
+            let mut n_halvings = 0;
+            let mut remaining = candidates.len();
+            while remaining > 1 {
+                remaining = remaining.div_ceil(factor);
+                n_halvings += 1;
+            }
+            let mut min_resources = (max_resources / factor.pow(n_halvings)).max(1);
+            if *resource == Resource::Rows {
+                while min_resources < max_resources && !splittable(min_resources) {
+                    min_resources += 1;
+                }
+            }
+            min_resources
+        }
+    };
+    if min_resources > max_resources {
+        return Err(ValidationError(ErrString::from(
+            "min_resources is larger than the available resources",
+        )));
+    }
+    if *resource == Resource::Rows && !splittable(min_resources) {
+        return Err(ValidationError(ErrString::from(format!(
+            "the splitter cannot cross validate a first round of {min_resources} rows"
+        ))));
+    }
+
+    let mut rounds: Vec<HalvingRound> = Vec::new();
+    let mut n_resources = min_resources;
+    loop {
+        let results = match resource {
+            Resource::Rows => {
+                let rows = subsample(n_resources);
+                let x_sub: Vec<NamedArray<f64>> = x.iter().map(|nm| take_rows(nm, &rows)).collect();
+                let y_sub = take_rows(y, &rows);
+                evaluate_candidates(estimator, candidates, &x_sub, &y_sub, splitter, scorer)?
+            }
+            Resource::Param { name, .. } => {
+                let mut budgeted = estimator.clone();
+                budgeted.set_param(name, n_resources as f64)?;
+                evaluate_candidates(&budgeted, candidates, x, y, splitter, scorer)?
+            }
+        };
+
+        let n_keep = results.len().div_ceil(factor);
+        candidates = results
+            .iter()
+            .filter(|r| r.rank_test_score <= n_keep)
+            .map(|r| r.params.clone())
+            .collect();
+        rounds.push(HalvingRound {
+            n_resources,
+            results,
+        });
+
+        // a lone survivor still gets scored on the next budget, like every
+        // other round's winners
+        if rounds.last().unwrap().results.len() == 1 || n_resources == max_resources {
+            break;
+        }
+        n_resources = (n_resources * factor).min(max_resources);
+    }
+
+    // the winner is refit with the budget it was last scored on
+    let last = rounds.last().unwrap();
+    let mut final_estimator = estimator.clone();
+    if let Resource::Param { name, .. } = resource {
+        final_estimator.set_param(name, last.n_resources as f64)?;
+    }
+    let best = refit_best(&final_estimator, last.results.clone(), x, y)?;
+    Ok(HalvingSearchReturn {
+        rounds,
+        best_params: best.best_params,
+        best_score: best.best_score,
+        best_estimator: best.best_estimator,
+    })
+}
+
+impl<E, S> HalvingGridSearch<E, S>
+where
+    E: Estimator,
+    S: CrossValidator,
+{
+    pub fn new(
+        estimator: E,
+        param_grid: HashMap<String, Vec<f64>>,
+        splitter: S,
+        scorer: Scorer,
+    ) -> Result<Self> {
+        assert_logical_param_grid(&param_grid)?;
+        Ok(Self {
+            estimator,
+            param_grid,
+            splitter,
+            scorer,
+            schedule: HalvingSchedule::new(None),
+        })
+    }
+
+    pub fn with_factor(mut self, factor: usize) -> Result<Self> {
+        assert_logical_factor(factor)?;
+        self.schedule.factor = factor;
+        Ok(self)
+    }
+
+    pub fn with_resource(
+        mut self,
+        resource: Resource,
+        min_resources: Option<usize>,
+    ) -> Result<Self> {
+        assert_logical_resource(&resource, min_resources)?;
+        self.schedule.resource = resource;
+        self.schedule.min_resources = min_resources;
+        Ok(self)
+    }
+
+    pub fn with_random_state(mut self, random_state: Option<u64>) -> Self {
+        self.schedule.random_state = random_state;
+        self
+    }
+
+    pub fn fit<T>(&self, x: &[NamedArray<T>], y: &NamedArray<T>) -> Result<HalvingSearchReturn<E>>
+    where
+        T: ToPrimitive,
+    {
+        successive_halving(
+            &self.estimator,
+            grid_candidates(&self.param_grid),
+            x,
+            y,
+            &self.splitter,
+            &self.scorer,
+            &self.schedule,
+        )
+    }
+}
+
+impl<E, S> HalvingRandomSearch<E, S>
+where
+    E: Estimator,
+    S: CrossValidator,
+{
+    pub fn new(
+        estimator: E,
+        param_distributions: HashMap<String, Distribution>,
+        n_candidates: usize,
+        splitter: S,
+        scorer: Scorer,
+        random_state: Option<u64>,
+    ) -> Result<Self> {
+        assert_logical_param_distributions(&param_distributions)?;
+        if n_candidates == 0 {
+            return Err(ValidationError(ErrString::from(
+                "n_candidates must be at least 1",
+            )));
+        }
+        Ok(Self {
+            estimator,
+            param_distributions,
+            n_candidates,
+            splitter,
+            scorer,
+            schedule: HalvingSchedule::new(random_state),
+        })
+    }
+
+    pub fn with_factor(mut self, factor: usize) -> Result<Self> {
+        assert_logical_factor(factor)?;
+        self.schedule.factor = factor;
+        Ok(self)
+    }
+
+    pub fn with_resource(
+        mut self,
+        resource: Resource,
+        min_resources: Option<usize>,
+    ) -> Result<Self> {
+        assert_logical_resource(&resource, min_resources)?;
+        self.schedule.resource = resource;
+        self.schedule.min_resources = min_resources;
+        Ok(self)
+    }
+
+    pub fn with_random_state(mut self, random_state: Option<u64>) -> Self {
+        self.schedule.random_state = random_state;
+        self
+    }
+
+    pub fn fit<T>(&self, x: &[NamedArray<T>], y: &NamedArray<T>) -> Result<HalvingSearchReturn<E>>
+    where
+        T: ToPrimitive,
+    {
+        let candidates = sample_candidates(
+            &self.param_distributions,
+            self.n_candidates,
+            self.schedule.random_state,
+        );
+        successive_halving(
+            &self.estimator,
+            candidates,
+            x,
+            y,
+            &self.splitter,
+            &self.scorer,
+            &self.schedule,
+        )
+    }
+}
+
+// the search result type is shared with the exhaustive searches so callers
+// can treat the final round like any other search
+impl<E> From<HalvingSearchReturn<E>> for SearchReturn<E> {
+    fn from(halving: HalvingSearchReturn<E>) -> Self {
+        let results = halving.rounds.last().unwrap().results.clone();
+        let best_index = results.iter().position(|r| r.rank_test_score == 1).unwrap();
+        SearchReturn {
+            results,
+            best_index,
+            best_params: halving.best_params,
+            best_score: halving.best_score,
+            best_estimator: halving.best_estimator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_validate::tests::MeanEstimator;
+    use crate::cross_validation::kfold::KFold;
+    use assert_float_eq::assert_float_relative_eq;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: (0..36).map(|i| i as f64).collect(),
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: (0..36).map(|i| 10.0 + (i % 3) as f64).collect(),
+        }
+    }
+
+    #[fixture]
+    fn grid() -> HashMap<String, Vec<f64>> {
+        HashMap::from([(
+            "shrinkage".to_string(),
+            vec![-0.4, -0.3, -0.2, -0.1, 0.0, 0.1, 0.2, 0.3, 0.4],
+        )])
+    }
+
+    #[rstest]
+    fn test_halving_grid_search(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+        grid: HashMap<String, Vec<f64>>,
+    ) {
+        let search = HalvingGridSearch::new(
+            MeanEstimator::default(),
+            grid,
+            KFold::new(2).unwrap(),
+            Scorer::MeanSquaredError,
+        )
+        .unwrap()
+        .with_random_state(Some(5));
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+
+        // 9 -> 3 -> 1 candidates on 4, 12 then 36 rows
+        let sizes: Vec<(usize, usize)> = res
+            .rounds
+            .iter()
+            .map(|r| (r.n_resources, r.results.len()))
+            .collect();
+        assert_eq!(sizes, vec![(4, 9), (12, 3), (36, 1)]);
+        assert_eq!(res.best_params["shrinkage"], 0.0);
+        assert_eq!(res.best_estimator.shrinkage, 0.0);
+        assert_eq!(res.best_estimator.mean, 11.0);
+
+        let flat: SearchReturn<MeanEstimator> = res.into();
+        assert_eq!(flat.results.len(), 1);
+    }
+
+    // MeanEstimator whose predictions are off by 1 / budget, so a larger
+    // budget always scores better
+    #[derive(Debug, PartialEq, Clone)]
+    struct BudgetEstimator {
+        inner: MeanEstimator,
+        budget: f64,
+    }
+
+    impl Default for BudgetEstimator {
+        fn default() -> Self {
+            Self {
+                inner: MeanEstimator::default(),
+                budget: 1.0,
+            }
+        }
+    }
+
+    impl Estimator for BudgetEstimator {
+        fn fit(&mut self, x: &[NamedArray<f64>], y: &NamedArray<f64>) -> Result<()> {
+            self.inner.fit(x, y)
+        }
+
+        fn predict(&self, x: &[NamedArray<f64>]) -> Result<NamedArray<f64>> {
+            let predictions = self.inner.predict(x)?;
+            Ok(predictions.map("predictions", |p| p + 1.0 / self.budget))
+        }
+
+        fn set_param(&mut self, name: &str, value: f64) -> Result<()> {
+            match name {
+                "budget" => {
+                    self.budget = value;
+                    Ok(())
+                }
+                _ => self.inner.set_param(name, value),
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_halving_param_resource_refit(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+        grid: HashMap<String, Vec<f64>>,
+    ) {
+        let search = HalvingGridSearch::new(
+            BudgetEstimator::default(),
+            grid,
+            KFold::new(3).unwrap(),
+            Scorer::MeanSquaredError,
+        )
+        .unwrap()
+        .with_factor(2)
+        .unwrap()
+        .with_resource(
+            Resource::Param {
+                name: "budget".to_string(),
+                max: 8,
+            },
+            Some(1),
+        )
+        .unwrap();
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+
+        let sizes: Vec<(usize, usize)> = res
+            .rounds
+            .iter()
+            .map(|r| (r.n_resources, r.results.len()))
+            .collect();
+        assert_eq!(sizes, vec![(1, 9), (2, 5), (4, 3), (8, 2)]);
+        assert_eq!(res.best_params["shrinkage"], 0.0);
+        // refit with the final budget rather than the estimator's default
+        assert_eq!(res.best_estimator.budget, 8.0);
+        assert_eq!(res.best_estimator.inner.mean, 11.0);
+        // the spread of the target around its mean plus the 1 / 8 offset
+        assert_float_relative_eq!(res.best_score, 2.0 / 3.0 + 0.015625, 0.001);
+    }
+
+    #[rstest]
+    fn test_halving_min_resources_clamped(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+        grid: HashMap<String, Vec<f64>>,
+    ) {
+        // 9 candidates with factor 3 would start on 36 / 9 = 4 rows, fewer
+        // than the 5 folds
+        let search = HalvingGridSearch::new(
+            MeanEstimator::default(),
+            grid.clone(),
+            KFold::new(5).unwrap(),
+            Scorer::MeanSquaredError,
+        )
+        .unwrap();
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+        assert_eq!(res.rounds[0].n_resources, 5);
+
+        let explicit = HalvingGridSearch::new(
+            MeanEstimator::default(),
+            grid,
+            KFold::new(5).unwrap(),
+            Scorer::MeanSquaredError,
+        )
+        .unwrap()
+        .with_resource(Resource::Rows, Some(3))
+        .unwrap();
+        assert!(explicit
+            .fit(&input_named_array, &target_named_array)
+            .is_err());
+    }
+
+    #[rstest]
+    fn test_halving_param_resource(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+        grid: HashMap<String, Vec<f64>>,
+    ) {
+        let search = HalvingGridSearch::new(
+            MeanEstimator::default(),
+            grid,
+            KFold::new(3).unwrap(),
+            Scorer::MeanSquaredError,
+        )
+        .unwrap()
+        .with_factor(2)
+        .unwrap()
+        .with_resource(
+            Resource::Param {
+                name: "budget".to_string(),
+                max: 8,
+            },
+            Some(1),
+        )
+        .unwrap();
+        // the test estimator has no budget parameter to receive
+        assert!(search.fit(&input_named_array, &target_named_array).is_err());
+    }
+
+    #[rstest]
+    fn test_halving_random_search(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let distributions = HashMap::from([(
+            "shrinkage".to_string(),
+            Distribution::Uniform {
+                low: -0.5,
+                high: 0.5,
+            },
+        )]);
+        let search = HalvingRandomSearch::new(
+            MeanEstimator::default(),
+            distributions,
+            6,
+            KFold::new(2).unwrap(),
+            Scorer::MeanAbsoluteError,
+            None,
+        )
+        .unwrap()
+        .with_random_state(Some(2))
+        .with_factor(2)
+        .unwrap()
+        .with_resource(Resource::Rows, Some(6))
+        .unwrap();
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+
+        let sizes: Vec<(usize, usize)> = res
+            .rounds
+            .iter()
+            .map(|r| (r.n_resources, r.results.len()))
+            .collect();
+        assert_eq!(sizes, vec![(6, 6), (12, 3), (24, 2), (36, 1)]);
+        // the kept candidates are the best ranked of the round before
+        let kept: Vec<f64> = res.rounds[1]
+            .results
+            .iter()
+            .map(|r| r.params["shrinkage"])
+            .collect();
+        for r in res.rounds[0].results.iter() {
+            assert_eq!(
+                kept.contains(&r.params["shrinkage"]),
+                r.rank_test_score <= 3
+            );
+        }
+        assert_eq!(
+            res,
+            search.fit(&input_named_array, &target_named_array).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_halving_errors(grid: HashMap<String, Vec<f64>>) {
+        let search = HalvingGridSearch::new(
+            MeanEstimator::default(),
+            grid,
+            KFold::new(2).unwrap(),
+            Scorer::R2,
+        )
+        .unwrap();
+        assert!(search.clone().with_factor(1).is_err());
+        assert!(search
+            .clone()
+            .with_resource(Resource::Rows, Some(0))
+            .is_err());
+        assert!(search
+            .with_resource(
+                Resource::Param {
+                    name: "max_iter".to_string(),
+                    max: 5,
+                },
+                Some(10),
+            )
+            .is_err());
+    }
+}
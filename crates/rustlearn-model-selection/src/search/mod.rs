@@ -0,0 +1,160 @@
+pub mod distributions;
+pub mod grid;
+pub mod halving;
+pub mod randomized;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::cross_validate::cross_val_score;
+use crate::cross_validation::CrossValidator;
+use crate::estimator::Estimator;
+use crate::scoring::Scorer;
+use crate::types::take_rows;
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError;
+
+pub type Result<X> = std::result::Result<X, RustLearnError>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CandidateResult {
+    pub params: HashMap<String, f64>,
+    pub test_scores: Vec<f64>,
+    pub mean_test_score: f64,
+    pub std_test_score: f64,
+    pub rank_test_score: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SearchReturn<E> {
+    pub results: Vec<CandidateResult>,
+    pub best_index: usize,
+    pub best_params: HashMap<String, f64>,
+    pub best_score: f64,
+    pub best_estimator: E,
+}
+
+pub fn with_params<E>(estimator: &E, params: &HashMap<String, f64>) -> Result<E>
+where
+    E: Estimator,
+{
+    let mut model = estimator.clone();
+    for (name, value) in params.iter() {
+        model.set_param(name, *value)?;
+    }
+    Ok(model)
+}
+
+pub fn evaluate_candidates<E, S, T>(
+    estimator: &E,
+    candidates: Vec<HashMap<String, f64>>,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+    splitter: &S,
+    scorer: &Scorer,
+) -> Result<Vec<CandidateResult>>
+where
+    E: Estimator,
+    S: CrossValidator,
+    T: ToPrimitive,
+{
+    let mut results: Vec<CandidateResult> = Vec::new();
+    for params in candidates {
+        let model = with_params(estimator, &params)?;
+        let scores = NamedArray {
+            name: scorer.name().to_string(),
+            data: cross_val_score(&model, x, y, splitter, scorer)?,
+        };
+        results.push(CandidateResult {
+            params,
+            mean_test_score: scores.mean()?,
+            std_test_score: scores.std(0)?,
+            test_scores: scores.data,
+            rank_test_score: 0,
+        });
+    }
+
+    rank_candidates(&mut results, scorer.greater_is_better());
+    Ok(results)
+}
+
+// rank 1 is the best candidate, whichever direction the scorer improves in;
+// a NaN mean, such as from a fold with a constant target, always ranks last
+pub fn rank_candidates(results: &mut [CandidateResult], greater_is_better: bool) {
+    let mut order: Vec<usize> = (0..results.len()).collect();
+    order.sort_by(|a, b| {
+        let (a, b) = (results[*a].mean_test_score, results[*b].mean_test_score);
+        match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) if greater_is_better => b.total_cmp(&a),
+            (false, false) => a.total_cmp(&b),
+        }
+    });
+    for (rank, i) in order.iter().enumerate() {
+        results[*i].rank_test_score = rank + 1;
+    }
+}
+
+// picks the winner of a finished search and refits it on every row
+pub fn refit_best<E, T>(
+    estimator: &E,
+    results: Vec<CandidateResult>,
+    x: &[NamedArray<T>],
+    y: &NamedArray<T>,
+) -> Result<SearchReturn<E>>
+where
+    E: Estimator,
+    T: ToPrimitive,
+{
+    let best_index = results.iter().position(|r| r.rank_test_score == 1).unwrap();
+    let best_params = results[best_index].params.clone();
+    let mut best_estimator = with_params(estimator, &best_params)?;
+
+    let rows: Vec<usize> = (0..y.data.len()).collect();
+    let x_all: Vec<NamedArray<f64>> = x.iter().map(|nm| take_rows(nm, &rows)).collect();
+    best_estimator.fit(&x_all, &take_rows(y, &rows))?;
+
+    Ok(SearchReturn {
+        best_score: results[best_index].mean_test_score,
+        results,
+        best_index,
+        best_params,
+        best_estimator,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn candidate(mean_test_score: f64) -> CandidateResult {
+        CandidateResult {
+            params: HashMap::new(),
+            test_scores: vec![mean_test_score],
+            mean_test_score,
+            std_test_score: 0.0,
+            rank_test_score: 0,
+        }
+    }
+
+    #[rstest]
+    fn test_rank_candidates_nan_last() {
+        let mut results = vec![candidate(0.5), candidate(f64::NAN), candidate(0.9)];
+        rank_candidates(&mut results, true);
+        let ranks: Vec<usize> = results.iter().map(|r| r.rank_test_score).collect();
+        assert_eq!(ranks, vec![2, 3, 1]);
+
+        rank_candidates(&mut results, false);
+        let ranks: Vec<usize> = results.iter().map(|r| r.rank_test_score).collect();
+        assert_eq!(ranks, vec![1, 3, 2]);
+
+        // negative NaN sorts below every number under total_cmp
+        let mut results = vec![candidate(-f64::NAN), candidate(4.0)];
+        rank_candidates(&mut results, false);
+        assert_eq!(results[0].rank_test_score, 2);
+    }
+}
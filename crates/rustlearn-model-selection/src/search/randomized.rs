@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::cross_validation::CrossValidator;
+use crate::estimator::Estimator;
+use crate::rng::seeded_rng;
+use crate::scoring::Scorer;
+use crate::search::distributions::Distribution;
+use crate::search::{evaluate_candidates, refit_best, Result, SearchReturn};
+use num::ToPrimitive;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::ErrString;
+use rustlearn_errors::RustLearnError::ValidationError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RandomizedSearchCV<E, S> {
+    pub estimator: E,
+    pub param_distributions: HashMap<String, Distribution>,
+    pub n_iter: usize,
+    pub splitter: S,
+    pub scorer: Scorer,
+    pub random_state: Option<u64>,
+}
+
+pub fn assert_logical_param_distributions(
+    param_distributions: &HashMap<String, Distribution>,
+) -> Result<()> {
+    if param_distributions.is_empty() {
+        return Err(ValidationError(ErrString::from(
+            "param_distributions is empty",
+        )));
+    }
+    for distribution in param_distributions.values() {
+        distribution.validate()?;
+    }
+    Ok(())
+}
+
+// samples parameters in name order so a seed always gives the same candidates
+pub fn sample_candidates(
+    param_distributions: &HashMap<String, Distribution>,
+    n_iter: usize,
+    random_state: Option<u64>,
+) -> Vec<HashMap<String, f64>> {
+    let mut names: Vec<&String> = param_distributions.keys().collect();
+    names.sort();
+    let mut rng = seeded_rng(random_state);
+    (0..n_iter)
+        .map(|_| {
+            names
+                .iter()
+                .map(|name| {
+                    (
+                        name.to_string(),
+                        param_distributions[*name].sample(&mut rng),
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl<E, S> RandomizedSearchCV<E, S>
+where
+    E: Estimator,
+    S: CrossValidator,
+{
+    pub fn new(
+        estimator: E,
+        param_distributions: HashMap<String, Distribution>,
+        n_iter: usize,
+        splitter: S,
+        scorer: Scorer,
+        random_state: Option<u64>,
+    ) -> Result<Self> {
+        assert_logical_param_distributions(&param_distributions)?;
+        if n_iter == 0 {
+            return Err(ValidationError(ErrString::from(
+                "n_iter must be at least 1",
+            )));
+        }
+        Ok(Self {
+            estimator,
+            param_distributions,
+            n_iter,
+            splitter,
+            scorer,
+            random_state,
+        })
+    }
+
+    pub fn fit<T>(&self, x: &[NamedArray<T>], y: &NamedArray<T>) -> Result<SearchReturn<E>>
+    where
+        T: ToPrimitive,
+    {
+        let candidates =
+            sample_candidates(&self.param_distributions, self.n_iter, self.random_state);
+        let results = evaluate_candidates(
+            &self.estimator,
+            candidates,
+            x,
+            y,
+            &self.splitter,
+            &self.scorer,
+        )?;
+        refit_best(&self.estimator, results, x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cross_validate::tests::MeanEstimator;
+    use crate::cross_validation::kfold::KFold;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }]
+    }
+
+    #[fixture]
+    fn target_named_array() -> NamedArray<f64> {
+        NamedArray {
+            name: "target".to_string(),
+            data: vec![1.0, 2.0, 3.0, 4.0],
+        }
+    }
+
+    #[fixture]
+    fn search() -> RandomizedSearchCV<MeanEstimator, KFold> {
+        let distributions = HashMap::from([(
+            "shrinkage".to_string(),
+            Distribution::Uniform {
+                low: -1.0,
+                high: 1.0,
+            },
+        )]);
+        RandomizedSearchCV::new(
+            MeanEstimator::default(),
+            distributions,
+            8,
+            KFold::new(2).unwrap(),
+            Scorer::MeanAbsoluteError,
+            Some(11),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_randomized_search(
+        search: RandomizedSearchCV<MeanEstimator, KFold>,
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let res = search.fit(&input_named_array, &target_named_array).unwrap();
+        assert_eq!(res.results.len(), 8);
+        for r in res.results.iter() {
+            assert!((-1.0..1.0).contains(&r.params["shrinkage"]));
+            assert!(r.mean_test_score >= res.best_score);
+        }
+        assert_eq!(res.results[res.best_index].rank_test_score, 1);
+        assert_eq!(res.best_estimator.shrinkage, res.best_params["shrinkage"]);
+
+        // the same seed samples the same candidates
+        let again = search.fit(&input_named_array, &target_named_array).unwrap();
+        assert_eq!(res.results, again.results);
+    }
+
+    #[rstest]
+    fn test_randomized_search_errors() {
+        let bad = HashMap::from([("shrinkage".to_string(), Distribution::Choice(vec![]))]);
+        assert!(RandomizedSearchCV::new(
+            MeanEstimator::default(),
+            bad,
+            2,
+            KFold::new(2).unwrap(),
+            Scorer::R2,
+            None,
+        )
+        .is_err());
+
+        let good = HashMap::from([("shrinkage".to_string(), Distribution::Choice(vec![0.0]))]);
+        assert!(RandomizedSearchCV::new(
+            MeanEstimator::default(),
+            good,
+            0,
+            KFold::new(2).unwrap(),
+            Scorer::R2,
+            None,
+        )
+        .is_err());
+    }
+}
@@ -1,5 +1,7 @@
+use crate::rng::seeded_rng;
 use crate::types::TrainTestSplitReturn;
 use num::*;
+use rand::seq::SliceRandom;
 use rustlearn_array::namedarray::NamedArray;
 use rustlearn_errors::RustLearnError::ValidationError;
 use rustlearn_errors::{ErrString, RustLearnError};
@@ -9,6 +11,9 @@ pub struct SimpleTrainTestSplit<T> {
     pub x: Vec<NamedArray<T>>,
     pub y: NamedArray<T>,
     pub train_proportion: f64,
+    pub shuffle: bool,
+    pub random_state: Option<u64>,
+    pub test_size: Option<usize>,
 }
 
 pub type Result<SimpleTrainTestSplit> = std::result::Result<SimpleTrainTestSplit, RustLearnError>;
@@ -39,9 +44,24 @@ where
             x,
             y,
             train_proportion,
+            shuffle: false,
+            random_state: None,
+            test_size: None,
         })
     }
 
+    pub fn with_shuffle(mut self, random_state: Option<u64>) -> Self {
+        self.shuffle = true;
+        self.random_state = random_state;
+        self
+    }
+
+    pub fn with_test_size(mut self, test_size: usize) -> Result<Self> {
+        Self::assert_logical_test_size(test_size, self.y.data.len())?;
+        self.test_size = Some(test_size);
+        Ok(self)
+    }
+
     pub fn assert_equal_length(x: Vec<NamedArray<T>>, y: NamedArray<T>) -> Result<()>
     where
         T: ToPrimitive,
@@ -66,54 +86,36 @@ where
         Ok(())
     }
 
-    pub fn split(&mut self) -> Result<TrainTestSplitReturn> {
-        let x_clone = self.x.clone();
-        let y_clone = self.y.clone();
-        let index: usize = (y_clone.data.len().to_f64().unwrap() * self.train_proportion) as usize;
-
-        let mut x_train: Vec<NamedArray<f64>> = Vec::new();
-        let mut x_test: Vec<NamedArray<f64>> = Vec::new();
-
-        for x in x_clone.iter() {
-            let temp_train = x.data[0..index]
-                .iter()
-                .map(|x| x.to_f64().unwrap())
-                .collect();
-            let temp_test = x.data[index..x.data.len()]
-                .iter()
-                .map(|x| x.to_f64().unwrap())
-                .collect();
-            x_train.push(NamedArray {
-                name: x.clone().name,
-                data: temp_train,
-            });
-            x_test.push(NamedArray {
-                name: x.clone().name,
-                data: temp_test,
-            });
+    pub fn assert_logical_test_size(test_size: usize, n_rows: usize) -> Result<()> {
+        if test_size == 0 || test_size >= n_rows {
+            return Err(ValidationError(ErrString::from(
+                "test_size must be between 0 and the number of rows",
+            )));
         }
+        Ok(())
+    }
 
-        let y_train: NamedArray<f64> = NamedArray {
-            name: y_clone.clone().name,
-            data: y_clone.data[0..index]
-                .iter()
-                .map(|x| x.to_f64().unwrap())
-                .collect(),
-        };
-        let y_test: NamedArray<f64> = NamedArray {
-            name: y_clone.clone().name,
-            data: y_clone.data[index..y_clone.data.len()]
-                .iter()
-                .map(|x| x.to_f64().unwrap())
-                .collect(),
+    pub fn split(&mut self) -> Result<TrainTestSplitReturn> {
+        let n_rows = self.y.data.len();
+        let index: usize = match self.test_size {
+            Some(test_size) => {
+                Self::assert_logical_test_size(test_size, n_rows)?;
+                n_rows - test_size
+            }
+            None => (n_rows.to_f64().unwrap() * self.train_proportion) as usize,
         };
 
-        Ok(TrainTestSplitReturn {
-            x_train,
-            x_test,
-            y_train,
-            y_test,
-        })
+        let mut rows: Vec<usize> = (0..n_rows).collect();
+        if self.shuffle {
+            rows.shuffle(&mut seeded_rng(self.random_state));
+        }
+
+        Ok(TrainTestSplitReturn::from_indices(
+            &self.x,
+            &self.y,
+            &rows[0..index],
+            &rows[index..n_rows],
+        ))
     }
 }
 
@@ -342,4 +344,81 @@ mod tests {
             assert_float_relative_eq!(t.to_owned(), expected.y_test.data[i], 0.001)
         }
     }
+
+    #[rstest]
+    fn test_split_shuffled_reproducible(
+        input_named_array_2d: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let splitter =
+            SimpleTrainTestSplit::new(input_named_array_2d, target_named_array.clone(), 0.6)
+                .unwrap()
+                .with_shuffle(Some(42));
+        let res = splitter.clone().split().unwrap();
+        let again = splitter.clone().split().unwrap();
+        assert_eq!(res, again);
+
+        assert_eq!(res.y_train.len(), 3);
+        assert_eq!(res.y_test.len(), 2);
+        let mut seen: Vec<f64> = res.y_train.data.clone();
+        seen.extend(res.y_test.data.clone());
+        seen.sort_by(|a, b| a.total_cmp(b));
+        let mut expected = target_named_array.data.clone();
+        expected.sort_by(|a, b| a.total_cmp(b));
+        assert_eq!(seen, expected);
+
+        // rows stay aligned across x and y
+        for (i, y) in res.y_train.data.iter().enumerate() {
+            let row = target_named_array.data.iter().position(|t| t == y).unwrap();
+            assert_eq!(res.x_train[0].data[i], splitter.x[0].data[row]);
+        }
+    }
+
+    #[rstest]
+    fn test_split_shuffled_seeds_differ() {
+        let x = vec![NamedArray {
+            name: "x".to_string(),
+            data: (0..100).map(|i| i as f64).collect(),
+        }];
+        let y = NamedArray {
+            name: "y".to_string(),
+            data: (0..100).map(|i| i as f64).collect(),
+        };
+        let first = SimpleTrainTestSplit::new(x.clone(), y.clone(), 0.5)
+            .unwrap()
+            .with_shuffle(Some(1))
+            .split()
+            .unwrap();
+        let second = SimpleTrainTestSplit::new(x, y, 0.5)
+            .unwrap()
+            .with_shuffle(Some(2))
+            .split()
+            .unwrap();
+        assert_ne!(first.y_train, second.y_train);
+    }
+
+    #[rstest]
+    fn test_split_test_size(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let mut splitter = SimpleTrainTestSplit::new(input_named_array, target_named_array, 0.5)
+            .unwrap()
+            .with_test_size(1)
+            .unwrap();
+        let res = splitter.split().unwrap();
+        assert_eq!(res.y_train.data, vec![151.0, 75.0, 141.0, 206.0]);
+        assert_eq!(res.y_test.data, vec![135.0]);
+    }
+
+    #[rstest]
+    fn test_test_size_wrong(
+        input_named_array: Vec<NamedArray<f64>>,
+        target_named_array: NamedArray<f64>,
+    ) {
+        let splitter =
+            SimpleTrainTestSplit::new(input_named_array, target_named_array, 0.5).unwrap();
+        assert!(splitter.clone().with_test_size(0).is_err());
+        assert!(splitter.with_test_size(5).is_err());
+    }
 }
@@ -0,0 +1,309 @@
+use crate::rng::seeded_rng;
+use crate::train_test_split::simple::SimpleTrainTestSplit;
+use crate::types::TrainTestSplitReturn;
+use num::*;
+use rand::seq::SliceRandom;
+use rustlearn_array::namedarray::NamedArray;
+use rustlearn_errors::RustLearnError::ValidationError;
+use rustlearn_errors::{ErrString, RustLearnError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct StratifiedTrainTestSplit<T> {
+    pub x: Vec<NamedArray<T>>,
+    pub y: NamedArray<T>,
+    pub train_proportion: f64,
+    pub random_state: Option<u64>,
+}
+
+pub type Result<StratifiedTrainTestSplit> =
+    std::result::Result<StratifiedTrainTestSplit, RustLearnError>;
+
+pub fn class_indices<T>(y: &NamedArray<T>) -> Vec<(f64, Vec<usize>)>
+where
+    T: ToPrimitive,
+{
+    // sort once rather than searching the classes for every row; the sort
+    // is stable so rows stay ascending within each class
+    let mut labelled: Vec<(f64, usize)> = y
+        .data
+        .iter()
+        .enumerate()
+        .map(|(i, label)| (label.to_f64().unwrap(), i))
+        .collect();
+    labelled.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut classes: Vec<(f64, Vec<usize>)> = Vec::new();
+    for (label, i) in labelled {
+        match classes.last_mut() {
+            Some((c, rows)) if *c == label => rows.push(i),
+            _ => classes.push((label, vec![i])),
+        }
+    }
+    classes
+}
+
+impl<T> StratifiedTrainTestSplit<T>
+where
+    T: Num + Copy + ToPrimitive,
+{
+    pub fn new(
+        x: Vec<NamedArray<T>>,
+        y: NamedArray<T>,
+        train_proportion: f64,
+        random_state: Option<u64>,
+    ) -> Result<Self> {
+        if y.data.is_empty() {
+            return Err(ValidationError(ErrString::from(
+                "target is an empty vector",
+            )));
+        }
+        if x.is_empty() {
+            return Err(ValidationError(ErrString::from("no x-values provided")));
+        }
+        SimpleTrainTestSplit::assert_equal_length(x.clone(), y.clone())?;
+        SimpleTrainTestSplit::<T>::assert_logical_train_proportion(train_proportion)?;
+        Ok(Self {
+            x,
+            y,
+            train_proportion,
+            random_state,
+        })
+    }
+
+    // largest remainder allocation of the training rows across classes,
+    // keeping at least one row of every class on each side of the split
+    fn train_counts(&self, classes: &[(f64, Vec<usize>)]) -> Result<Vec<usize>> {
+        let n_rows = self.y.data.len();
+        let n_train = (n_rows.to_f64().unwrap() * self.train_proportion) as usize;
+        if n_train < classes.len() || n_rows - n_train < classes.len() {
+            return Err(ValidationError(ErrString::from(format!(
+                "both partitions need at least {} rows to hold every class",
+                classes.len()
+            ))));
+        }
+
+        let mut counts: Vec<usize> = Vec::new();
+        let mut exacts: Vec<f64> = Vec::new();
+        let mut remainders: Vec<(f64, usize)> = Vec::new();
+        for (i, (label, rows)) in classes.iter().enumerate() {
+            if rows.len() < 2 {
+                return Err(ValidationError(ErrString::from(format!(
+                    "class {label} has {} member, at least 2 are needed",
+                    rows.len()
+                ))));
+            }
+            let exact = rows.len() as f64 * n_train as f64 / n_rows as f64;
+            counts.push(exact.floor() as usize);
+            exacts.push(exact);
+            remainders.push((exact - exact.floor(), i));
+        }
+        remainders.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let allocated: usize = counts.iter().sum();
+        for (_, i) in remainders.iter().take(n_train - allocated) {
+            counts[*i] += 1;
+        }
+
+        for (count, (_, rows)) in counts.iter_mut().zip(classes.iter()) {
+            *count = (*count).clamp(1, rows.len() - 1);
+        }
+        // clamping can move the total away from n_train, so hand the
+        // difference back one row at a time to the class furthest from its
+        // exact share that still has room; the checks above guarantee one
+        // exists
+        loop {
+            let allocated: usize = counts.iter().sum();
+            if allocated == n_train {
+                break;
+            }
+            let adjustable = (0..classes.len()).filter(|i| {
+                if allocated > n_train {
+                    counts[*i] > 1
+                } else {
+                    counts[*i] < classes[*i].1.len() - 1
+                }
+            });
+            if allocated > n_train {
+                let i = adjustable
+                    .max_by(|a, b| {
+                        (counts[*a] as f64 - exacts[*a])
+                            .total_cmp(&(counts[*b] as f64 - exacts[*b]))
+                    })
+                    .unwrap();
+                counts[i] -= 1;
+            } else {
+                let i = adjustable
+                    .max_by(|a, b| {
+                        (exacts[*a] - counts[*a] as f64)
+                            .total_cmp(&(exacts[*b] - counts[*b] as f64))
+                    })
+                    .unwrap();
+                counts[i] += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    pub fn split(&mut self) -> Result<TrainTestSplitReturn> {
+        let classes = class_indices(&self.y);
+        let counts = self.train_counts(&classes)?;
+        let mut rng = seeded_rng(self.random_state);
+
+        let mut train: Vec<usize> = Vec::new();
+        let mut test: Vec<usize> = Vec::new();
+        for ((_, rows), count) in classes.iter().zip(counts.iter()) {
+            let mut rows = rows.clone();
+            rows.shuffle(&mut rng);
+            train.extend_from_slice(&rows[0..*count]);
+            test.extend_from_slice(&rows[*count..rows.len()]);
+        }
+        train.sort();
+        test.sort();
+
+        Ok(TrainTestSplitReturn::from_indices(
+            &self.x, &self.y, &train, &test,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn input_named_array() -> Vec<NamedArray<f64>> {
+        vec![NamedArray {
+            name: "age".to_string(),
+            data: (0..20).map(|i| i as f64).collect(),
+        }]
+    }
+
+    #[fixture]
+    fn rare_event_target() -> NamedArray<f64> {
+        let mut data = vec![0.0; 20];
+        for i in [3, 11, 17, 19] {
+            data[i] = 1.0;
+        }
+        NamedArray {
+            name: "target".to_string(),
+            data,
+        }
+    }
+
+    fn count(y: &NamedArray<f64>, label: f64) -> usize {
+        y.data.iter().filter(|v| **v == label).count()
+    }
+
+    #[rstest]
+    fn test_class_indices(rare_event_target: NamedArray<f64>) {
+        let classes = class_indices(&rare_event_target);
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].0, 0.0);
+        assert_eq!(classes[1], (1.0, vec![3, 11, 17, 19]));
+    }
+
+    #[rstest]
+    fn test_split_keeps_proportions(
+        input_named_array: Vec<NamedArray<f64>>,
+        rare_event_target: NamedArray<f64>,
+    ) {
+        let mut splitter =
+            StratifiedTrainTestSplit::new(input_named_array, rare_event_target, 0.5, Some(7))
+                .unwrap();
+        let res = splitter.split().unwrap();
+
+        assert_eq!(res.y_train.len(), 10);
+        assert_eq!(res.y_test.len(), 10);
+        assert_eq!(count(&res.y_train, 1.0), 2);
+        assert_eq!(count(&res.y_test, 1.0), 2);
+
+        // rows stay aligned across x and y
+        for (x, y) in res.x_test[0].data.iter().zip(res.y_test.data.iter()) {
+            let expected = if [3.0, 11.0, 17.0, 19.0].contains(x) {
+                1.0
+            } else {
+                0.0
+            };
+            assert_eq!(*y, expected);
+        }
+    }
+
+    #[rstest]
+    fn test_split_reproducible(
+        input_named_array: Vec<NamedArray<f64>>,
+        rare_event_target: NamedArray<f64>,
+    ) {
+        let mut splitter =
+            StratifiedTrainTestSplit::new(input_named_array, rare_event_target, 0.7, Some(3))
+                .unwrap();
+        let first = splitter.split().unwrap();
+        let second = splitter.split().unwrap();
+        assert_eq!(first, second);
+        assert_eq!(count(&first.y_test, 1.0), 1);
+    }
+
+    #[rstest]
+    fn test_split_many_small_classes() {
+        // ten classes of two rows and one of twenty: every small class is
+        // clamped up to one training row, which the large class gives back
+        let mut data: Vec<f64> = (0..20).map(|i| (i / 2) as f64).collect();
+        data.extend(vec![10.0; 20]);
+        let y = NamedArray {
+            name: "target".to_string(),
+            data,
+        };
+        let x = vec![NamedArray {
+            name: "age".to_string(),
+            data: (0..40).map(|i| i as f64).collect(),
+        }];
+        let mut splitter = StratifiedTrainTestSplit::new(x, y, 0.3, Some(1)).unwrap();
+        let res = splitter.split().unwrap();
+        assert_eq!(res.y_train.len(), 12);
+        assert_eq!(res.y_test.len(), 28);
+        for label in 0..10 {
+            assert_eq!(count(&res.y_train, label as f64), 1);
+        }
+        assert_eq!(count(&res.y_train, 10.0), 2);
+    }
+
+    #[rstest]
+    fn test_class_too_small(input_named_array: Vec<NamedArray<f64>>) {
+        let mut data = vec![0.0; 20];
+        data[5] = 1.0;
+        let y = NamedArray {
+            name: "target".to_string(),
+            data,
+        };
+        let mut splitter = StratifiedTrainTestSplit::new(input_named_array, y, 0.5, None).unwrap();
+        assert!(splitter.split().is_err());
+    }
+
+    #[rstest]
+    fn test_partition_too_small(rare_event_target: NamedArray<f64>) {
+        let x = vec![NamedArray {
+            name: "age".to_string(),
+            data: vec![0.0; 20],
+        }];
+        let mut splitter = StratifiedTrainTestSplit::new(x, rare_event_target, 0.95, None).unwrap();
+        assert!(splitter.split().is_err());
+    }
+
+    #[rstest]
+    fn test_instantiation_errors(
+        input_named_array: Vec<NamedArray<f64>>,
+        rare_event_target: NamedArray<f64>,
+    ) {
+        assert!(StratifiedTrainTestSplit::new(
+            input_named_array.clone(),
+            rare_event_target.clone(),
+            1.0,
+            None
+        )
+        .is_err());
+        let short = NamedArray {
+            name: "target".to_string(),
+            data: vec![0.0, 1.0],
+        };
+        assert!(StratifiedTrainTestSplit::new(input_named_array, short, 0.5, None).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use num::ToPrimitive;
 use rustlearn_array::namedarray::NamedArray;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -7,3 +8,56 @@ pub struct TrainTestSplitReturn {
     pub x_test: Vec<NamedArray<f64>>,
     pub y_test: NamedArray<f64>,
 }
+
+pub fn take_rows<T>(named_array: &NamedArray<T>, rows: &[usize]) -> NamedArray<f64>
+where
+    T: ToPrimitive,
+{
+    NamedArray {
+        name: named_array.name.clone(),
+        data: rows
+            .iter()
+            .map(|i| named_array.data[*i].to_f64().unwrap())
+            .collect(),
+    }
+}
+
+impl TrainTestSplitReturn {
+    pub fn from_indices<T>(
+        x: &[NamedArray<T>],
+        y: &NamedArray<T>,
+        train: &[usize],
+        test: &[usize],
+    ) -> Self
+    where
+        T: ToPrimitive,
+    {
+        TrainTestSplitReturn {
+            x_train: x.iter().map(|nm| take_rows(nm, train)).collect(),
+            y_train: take_rows(y, train),
+            x_test: x.iter().map(|nm| take_rows(nm, test)).collect(),
+            y_test: take_rows(y, test),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+impl Fold {
+    // the training rows are every row not held out for testing
+    pub fn from_test(mut test: Vec<usize>, n_rows: usize) -> Self {
+        test.sort();
+        let mut held_out = vec![false; n_rows];
+        for i in test.iter() {
+            held_out[*i] = true;
+        }
+        Fold {
+            train: (0..n_rows).filter(|i| !held_out[*i]).collect(),
+            test,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::exceptions::{NotYetImplementedError, ValidationError};
+use crate::exceptions::{IoError, NotYetImplementedError, ValidationError};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use std::fmt::{Debug, Display, Formatter};
@@ -42,6 +42,7 @@ impl From<PyRustLearnError> for PyErr {
                 RustLearnError::NotYetImplementedError(err) => {
                     NotYetImplementedError::new_err(err.to_string())
                 }
+                RustLearnError::IoError(err) => IoError::new_err(err.to_string()),
             },
             Python(err) => err,
             err => PyRuntimeError::new_err(format!("{:?}", &err)),
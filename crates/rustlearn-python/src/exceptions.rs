@@ -12,3 +12,4 @@ create_exception!(
     NotYetImplementedError,
     RustLearnError
 );
+create_exception!(rust_kit_learn_core.exceptions, IoError, RustLearnError);
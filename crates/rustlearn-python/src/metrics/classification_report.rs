@@ -0,0 +1,76 @@
+use pyo3::intern;
+use pyo3::prelude::*;
+use rustlearn_metrics::classification_report::{
+    classification_report, ClassificationReport, ReportRow,
+};
+use std::collections::HashMap;
+
+use crate::array::PyNamedArray;
+use crate::errors::PyRustLearnError;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PyClassificationReport {
+    pub classification_report: ClassificationReport,
+}
+
+impl From<ClassificationReport> for PyClassificationReport {
+    fn from(classification_report: ClassificationReport) -> Self {
+        PyClassificationReport {
+            classification_report,
+        }
+    }
+}
+
+fn row_to_map(row: &ReportRow) -> HashMap<String, f64> {
+    HashMap::from([
+        ("precision".to_string(), row.precision),
+        ("recall".to_string(), row.recall),
+        ("f1_score".to_string(), row.f1_score),
+        ("support".to_string(), row.support as f64),
+    ])
+}
+
+#[pymethods]
+impl PyClassificationReport {
+    // per-class rows keyed by class name
+    pub fn classes(&self) -> HashMap<String, HashMap<String, f64>> {
+        self.classification_report
+            .classes
+            .iter()
+            .map(|row| (row.name.clone(), row_to_map(row)))
+            .collect()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        self.classification_report.accuracy
+    }
+
+    pub fn macro_avg(&self) -> HashMap<String, f64> {
+        row_to_map(&self.classification_report.macro_avg)
+    }
+
+    pub fn weighted_avg(&self) -> HashMap<String, f64> {
+        row_to_map(&self.classification_report.weighted_avg)
+    }
+
+    pub fn __str__(&self) -> String {
+        self.classification_report.to_string()
+    }
+}
+
+#[pyfunction]
+pub fn py_classification_report(
+    y_true: &Bound<'_, PyAny>,
+    y_pred: &Bound<'_, PyAny>,
+) -> PyResult<PyClassificationReport> {
+    let t_s = y_true.getattr(intern!(y_true.py(), "_n"))?;
+    let y_true_series = t_s.extract::<PyNamedArray>().unwrap().named_array;
+    let t_p = y_pred.getattr(intern!(y_pred.py(), "_n"))?;
+    let y_pred_series = t_p.extract::<PyNamedArray>().unwrap().named_array;
+    let res = match classification_report(y_true_series, y_pred_series) {
+        Ok(res) => res,
+        Err(e) => return Err(PyErr::from(PyRustLearnError::from(e))),
+    };
+    Ok(PyClassificationReport::from(res))
+}
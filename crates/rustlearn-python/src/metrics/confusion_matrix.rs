@@ -0,0 +1,79 @@
+use pyo3::intern;
+use pyo3::prelude::*;
+use rustlearn_errors::{ErrString, RustLearnError};
+use rustlearn_metrics::confusion_matrix::{confusion_matrix, ConfusionMatrix, Normalize};
+
+use crate::array::PyNamedArray;
+use crate::errors::PyRustLearnError;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PyConfusionMatrix {
+    pub confusion_matrix: ConfusionMatrix,
+}
+
+impl From<ConfusionMatrix> for PyConfusionMatrix {
+    fn from(confusion_matrix: ConfusionMatrix) -> Self {
+        PyConfusionMatrix { confusion_matrix }
+    }
+}
+
+#[pymethods]
+impl PyConfusionMatrix {
+    pub fn labels(&self) -> Vec<f64> {
+        self.confusion_matrix.labels.clone()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.confusion_matrix.names.clone()
+    }
+
+    pub fn matrix(&self) -> Vec<Vec<f64>> {
+        self.confusion_matrix.matrix.clone()
+    }
+
+    pub fn with_names(&self, names: Vec<String>) -> PyResult<Self> {
+        match self.confusion_matrix.clone().with_names(names) {
+            Ok(res) => Ok(PyConfusionMatrix::from(res)),
+            Err(e) => Err(PyErr::from(PyRustLearnError::from(e))),
+        }
+    }
+
+    pub fn __str__(&self) -> String {
+        self.confusion_matrix.to_string()
+    }
+}
+
+fn parse_normalize(normalize: Option<String>) -> Result<Option<Normalize>, RustLearnError> {
+    match normalize.as_deref() {
+        None => Ok(None),
+        Some("true") => Ok(Some(Normalize::True)),
+        Some("pred") => Ok(Some(Normalize::Pred)),
+        Some("all") => Ok(Some(Normalize::All)),
+        Some(other) => Err(RustLearnError::ValidationError(ErrString::from(format!(
+            "normalize must be one of true, pred or all, got {other}"
+        )))),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (y_true, y_pred, normalize=None))]
+pub fn py_confusion_matrix(
+    y_true: &Bound<'_, PyAny>,
+    y_pred: &Bound<'_, PyAny>,
+    normalize: Option<String>,
+) -> PyResult<PyConfusionMatrix> {
+    let t_s = y_true.getattr(intern!(y_true.py(), "_n"))?;
+    let y_true_series = t_s.extract::<PyNamedArray>().unwrap().named_array;
+    let t_p = y_pred.getattr(intern!(y_pred.py(), "_n"))?;
+    let y_pred_series = t_p.extract::<PyNamedArray>().unwrap().named_array;
+    let normalize = match parse_normalize(normalize) {
+        Ok(normalize) => normalize,
+        Err(e) => return Err(PyErr::from(PyRustLearnError::from(e))),
+    };
+    let res = match confusion_matrix(y_true_series, y_pred_series, normalize) {
+        Ok(res) => res,
+        Err(e) => return Err(PyErr::from(PyRustLearnError::from(e))),
+    };
+    Ok(PyConfusionMatrix::from(res))
+}
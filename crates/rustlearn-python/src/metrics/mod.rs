@@ -1,3 +1,5 @@
+pub mod classification_report;
+pub mod confusion_matrix;
 pub mod mean_absolute_error;
 pub mod mean_squared_error;
 pub mod r_2;
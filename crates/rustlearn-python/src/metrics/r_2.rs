@@ -6,12 +6,26 @@ use crate::array::PyNamedArray;
 use crate::errors::PyRustLearnError;
 
 #[pyfunction]
-pub fn py_r_2(y_true: &Bound<'_, PyAny>, y_pred: &Bound<'_, PyAny>) -> PyResult<f64> {
+#[pyo3(signature = (y_true, y_pred, sample_weight=None))]
+pub fn py_r_2(
+    y_true: &Bound<'_, PyAny>,
+    y_pred: &Bound<'_, PyAny>,
+    sample_weight: Option<&Bound<'_, PyAny>>,
+) -> PyResult<f64> {
     let t_s = y_true.getattr(intern!(y_true.py(), "_n"))?;
     let y_true_series = t_s.extract::<PyNamedArray>().unwrap().named_array;
     let t_p = y_pred.getattr(intern!(y_pred.py(), "_n"))?;
     let y_pred_series = t_p.extract::<PyNamedArray>().unwrap().named_array;
-    let res = match r_2(y_true_series, y_pred_series) {
+    let weights = match sample_weight {
+        Some(w) => Some(
+            w.getattr(intern!(w.py(), "_n"))?
+                .extract::<PyNamedArray>()
+                .unwrap()
+                .named_array,
+        ),
+        None => None,
+    };
+    let res = match r_2(y_true_series, y_pred_series, weights) {
         Ok(res) => res,
         Err(e) => return Err(PyErr::from(PyRustLearnError::from(e))),
     };
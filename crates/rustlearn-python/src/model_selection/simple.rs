@@ -11,6 +11,9 @@ pub struct PySimpleTrainTestSplit {
     pub x: Vec<PyNamedArray>,
     pub y: PyNamedArray,
     pub train_proportion: f64,
+    pub shuffle: bool,
+    pub random_state: Option<u64>,
+    pub test_size: Option<usize>,
 }
 
 impl From<SimpleTrainTestSplit<f64>> for PySimpleTrainTestSplit {
@@ -25,6 +28,9 @@ impl From<SimpleTrainTestSplit<f64>> for PySimpleTrainTestSplit {
                 named_array: simple_train_test_split.y,
             },
             train_proportion: simple_train_test_split.train_proportion,
+            shuffle: simple_train_test_split.shuffle,
+            random_state: simple_train_test_split.random_state,
+            test_size: simple_train_test_split.test_size,
         }
     }
 }
@@ -32,15 +38,22 @@ impl From<SimpleTrainTestSplit<f64>> for PySimpleTrainTestSplit {
 #[pymethods]
 impl PySimpleTrainTestSplit {
     #[new]
+    #[pyo3(signature = (x, y, train_proportion, shuffle=false, random_state=None, test_size=None))]
     pub fn __init__(
         x: Vec<PyNamedArray>,
         y: PyNamedArray,
         train_proportion: f64,
+        shuffle: bool,
+        random_state: Option<u64>,
+        test_size: Option<usize>,
     ) -> PyResult<Self> {
         let multiple_linear = PySimpleTrainTestSplit {
             x: x.clone(),
             y: y.clone(),
             train_proportion,
+            shuffle,
+            random_state,
+            test_size,
         };
         let _ = Self::assert_equal_length(x.clone(), y.clone());
         let _ = Self::assert_logical_train_proportion(train_proportion);
@@ -72,6 +85,15 @@ impl PySimpleTrainTestSplit {
         }
     }
 
+    #[staticmethod]
+    pub fn assert_logical_test_size(test_size: usize, n_rows: usize) -> PyResult<()> {
+        let asserted = SimpleTrainTestSplit::<f64>::assert_logical_test_size(test_size, n_rows);
+        match asserted {
+            Ok(_asserted) => Ok(()),
+            Err(e) => Err(PyErr::from(PyRustLearnError::RustLearn(e))),
+        }
+    }
+
     pub fn split(&self) -> PyResult<PyTrainTestSplitReturn> {
         let mut x_clone: Vec<NamedArray<f64>> = Vec::new();
         for v in self.x.clone().iter() {
@@ -81,8 +103,14 @@ impl PySimpleTrainTestSplit {
             x: x_clone.clone(),
             y: self.y.named_array.clone(),
             train_proportion: self.train_proportion,
+            shuffle: self.shuffle,
+            random_state: self.random_state,
+            test_size: self.test_size,
+        };
+        let res = match simple.split() {
+            Ok(res) => res,
+            Err(e) => return Err(PyErr::from(PyRustLearnError::RustLearn(e))),
         };
-        let res = simple.split().unwrap();
         Ok(PyTrainTestSplitReturn::from(res))
     }
 }
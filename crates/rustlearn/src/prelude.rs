@@ -1,7 +1,29 @@
 pub use rustlearn_array::*;
 pub use rustlearn_errors::*;
+pub use rustlearn_linear_model::ols::estimator::LinearRegressionEstimator;
 pub use rustlearn_linear_model::ols::linear_regression::*;
+pub use rustlearn_linear_model::ols::sparse_linear_regression::SparseLinearRegression;
 pub use rustlearn_linear_model::types::*;
 pub use rustlearn_metrics::*;
+pub use rustlearn_model_selection::bootstrap::{
+    bootstrap_confidence_interval, Bootstrap, ConfidenceInterval, IntervalMethod,
+};
+pub use rustlearn_model_selection::cross_validate::{
+    cross_val_score, cross_validate, CrossValidateReturn,
+};
+pub use rustlearn_model_selection::cross_validation::{
+    group_kfold::*, kfold::*, leave_one_out::*, materialize_folds, stratified_kfold::*,
+    time_series::*, CrossValidator,
+};
+pub use rustlearn_model_selection::curves::{
+    learning_curve, validation_curve, LearningCurveReturn, ValidationCurveReturn,
+};
+pub use rustlearn_model_selection::estimator::Estimator;
+pub use rustlearn_model_selection::permutation::{permutation_test_score, PermutationTestReturn};
+pub use rustlearn_model_selection::scoring::Scorer;
+pub use rustlearn_model_selection::search::{
+    distributions::Distribution, grid::GridSearchCV, halving::*, randomized::RandomizedSearchCV,
+    CandidateResult, SearchReturn,
+};
 pub use rustlearn_model_selection::train_test_split::*;
 pub use rustlearn_model_selection::types::*;
@@ -1,5 +1,10 @@
 """Exceptions for rustlearn."""
 
-from rustylearn import NotYetImplementedError, RustLearnError, ValidationError
+from rustylearn import (
+    IoError,
+    NotYetImplementedError,
+    RustLearnError,
+    ValidationError,
+)
 
-__all__ = ["RustLearnError", "ValidationError", "NotYetImplementedError"]
+__all__ = ["RustLearnError", "ValidationError", "NotYetImplementedError", "IoError"]
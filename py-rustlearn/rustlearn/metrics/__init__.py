@@ -1,17 +1,25 @@
 """Expose the metrics API."""
 
+from rustlearn.metrics.classification_report import classification_report
+from rustlearn.metrics.confusion_matrix import confusion_matrix
 from rustlearn.metrics.mean_absolute_error import mean_absolute_error
 from rustlearn.metrics.mean_squared_error import (
     mean_squared_error,
     root_mean_squared_error,
 )
 from rustlearn.metrics.r_2 import r_2
+from rustlearn.metrics.types import ClassificationReport, ConfusionMatrix
 
 
 __all__ = [
     # Metrics
+    "classification_report",
+    "confusion_matrix",
     "mean_absolute_error",
     "mean_squared_error",
     "root_mean_squared_error",
     "r_2",
+    # Return Objects
+    "ClassificationReport",
+    "ConfusionMatrix",
 ]
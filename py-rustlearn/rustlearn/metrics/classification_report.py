@@ -0,0 +1,24 @@
+"""Classification report between two NamedArrays."""
+
+from rustlearn import NamedArray
+from rustlearn.metrics.types import ClassificationReport
+from rustylearn import py_classification_report
+
+
+def classification_report(
+    y_true: NamedArray, y_pred: NamedArray
+) -> ClassificationReport:
+    """
+    Classification report calculation.
+
+    Precision, recall, F1 and support for every class along with the
+    accuracy and the macro and weighted averages. These arrays must be
+    the same length.
+
+    :param:
+        y_true: (NamedArray) the true labels
+        y_pred: (NamedArray) the predicted labels
+    :return:
+        (ClassificationReport)
+    """
+    return ClassificationReport(py_classification_report(y_true, y_pred))
@@ -0,0 +1,27 @@
+"""Confusion matrix between two NamedArrays."""
+
+from typing import Optional
+
+from rustlearn import NamedArray
+from rustlearn.metrics.types import ConfusionMatrix
+from rustylearn import py_confusion_matrix
+
+
+def confusion_matrix(
+    y_true: NamedArray, y_pred: NamedArray, normalize: Optional[str] = None
+) -> ConfusionMatrix:
+    """
+    Confusion matrix calculation.
+
+    Counts how often each true label was predicted as each label. These
+    arrays must be the same length.
+
+    :param:
+        y_true: (NamedArray) the true labels
+        y_pred: (NamedArray) the predicted labels
+        normalize: (Optional[str]) "true" to normalize rows, "pred" to
+            normalize columns or "all" to normalize the whole matrix
+    :return:
+        (ConfusionMatrix)
+    """
+    return ConfusionMatrix(py_confusion_matrix(y_true, y_pred, normalize))
@@ -1,10 +1,16 @@
 """Mean absolute error metric."""
 
+from typing import Optional
+
 from rustlearn import NamedArray
 from rustylearn import py_mean_absolute_error
 
 
-def mean_absolute_error(y_true: NamedArray, y_pred: NamedArray) -> float:
+def mean_absolute_error(
+    y_true: NamedArray,
+    y_pred: NamedArray,
+    sample_weight: Optional[NamedArray] = None,
+) -> float:
     """
     Mean absolute error calculation.
 
@@ -14,7 +20,8 @@ def mean_absolute_error(y_true: NamedArray, y_pred: NamedArray) -> float:
     :param:
         y_true: (NamedArray) the true values
         y_pred: (NamedArray) the predicted values
+        sample_weight: (Optional[NamedArray]) non-negative weight per row
     :return:
         (float)
     """
-    return py_mean_absolute_error(y_true, y_pred)
+    return py_mean_absolute_error(y_true, y_pred, sample_weight)
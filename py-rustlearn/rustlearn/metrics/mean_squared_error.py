@@ -1,10 +1,16 @@
 """Mean squared error and root mean squared error."""
 
+from typing import Optional
+
 from rustlearn import NamedArray
 from rustylearn import py_mean_squared_error, py_root_mean_squared_error
 
 
-def mean_squared_error(y_true: NamedArray, y_pred: NamedArray) -> float:
+def mean_squared_error(
+    y_true: NamedArray,
+    y_pred: NamedArray,
+    sample_weight: Optional[NamedArray] = None,
+) -> float:
     """
     Mean squared error calculation.
 
@@ -14,13 +20,18 @@ def mean_squared_error(y_true: NamedArray, y_pred: NamedArray) -> float:
     :param:
         y_true: (NamedArray) the true values
         y_pred: (NamedArray) the predicted values
+        sample_weight: (Optional[NamedArray]) non-negative weight per row
     :return:
         (float)
     """
-    return py_mean_squared_error(y_true, y_pred)
+    return py_mean_squared_error(y_true, y_pred, sample_weight)
 
 
-def root_mean_squared_error(y_true: NamedArray, y_pred: NamedArray) -> float:
+def root_mean_squared_error(
+    y_true: NamedArray,
+    y_pred: NamedArray,
+    sample_weight: Optional[NamedArray] = None,
+) -> float:
     """
     Root mean squared error calculation.
 
@@ -30,7 +41,8 @@ def root_mean_squared_error(y_true: NamedArray, y_pred: NamedArray) -> float:
     :param:
         y_true: (NamedArray) the true values
         y_pred: (NamedArray) the predicted values
+        sample_weight: (Optional[NamedArray]) non-negative weight per row
     :return:
         (float)
     """
-    return py_root_mean_squared_error(y_true, y_pred)
+    return py_root_mean_squared_error(y_true, y_pred, sample_weight)
@@ -1,10 +1,16 @@
 """R2 calculation from two NamedArrays."""
 
+from typing import Optional
+
 from rustlearn import NamedArray
 from rustylearn import py_r_2
 
 
-def r_2(y_true: NamedArray, y_pred: NamedArray) -> float:
+def r_2(
+    y_true: NamedArray,
+    y_pred: NamedArray,
+    sample_weight: Optional[NamedArray] = None,
+) -> float:
     """
     R2 calculation.
 
@@ -14,7 +20,8 @@ def r_2(y_true: NamedArray, y_pred: NamedArray) -> float:
     :param:
         y_true: (NamedArray) the true values
         y_pred: (NamedArray) the predicted values
+        sample_weight: (Optional[NamedArray]) non-negative weight per row
     :return:
         (float)
     """
-    return py_r_2(y_true, y_pred)
+    return py_r_2(y_true, y_pred, sample_weight)
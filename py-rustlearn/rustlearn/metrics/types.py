@@ -0,0 +1,62 @@
+"""Return types for the classification metrics."""
+
+from typing import Dict, List, Self
+
+from rustylearn import PyClassificationReport, PyConfusionMatrix
+
+
+class ConfusionMatrix:
+    """Confusion matrix with true labels as rows and predictions as columns."""
+
+    _n: PyConfusionMatrix = None
+
+    def __init__(self, py_confusion_matrix: PyConfusionMatrix) -> None:
+        """Instantiate the class.
+
+        :params:
+            py_confusion_matrix: (PyConfusionMatrix)
+        :return:
+            None
+        """
+        self._n = py_confusion_matrix
+        self.labels: List[float] = self._n.labels()
+        self.names: List[str] = self._n.names()
+        self.matrix: List[List[float]] = self._n.matrix()
+
+    def with_names(self, names: List[str]) -> Self:
+        """Replace the class names shown on both axes.
+
+        :params:
+            names: (List[str]) one name per label, in label order
+        :return:
+            ConfusionMatrix
+        """
+        return ConfusionMatrix(self._n.with_names(names))
+
+    def __str__(self) -> str:
+        """Render the matrix as a table."""
+        return str(self._n)
+
+
+class ClassificationReport:
+    """Per-class precision, recall, F1 and support."""
+
+    _n: PyClassificationReport = None
+
+    def __init__(self, py_classification_report: PyClassificationReport) -> None:
+        """Instantiate the class.
+
+        :params:
+            py_classification_report: (PyClassificationReport)
+        :return:
+            None
+        """
+        self._n = py_classification_report
+        self.classes: Dict[str, Dict[str, float]] = self._n.classes()
+        self.accuracy: float = self._n.accuracy()
+        self.macro_avg: Dict[str, float] = self._n.macro_avg()
+        self.weighted_avg: Dict[str, float] = self._n.weighted_avg()
+
+    def __str__(self) -> str:
+        """Render the report as a table."""
+        return str(self._n)
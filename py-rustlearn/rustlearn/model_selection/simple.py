@@ -1,6 +1,6 @@
 """Simple train test splitter."""
 
-from typing import List, Self
+from typing import List, Optional, Self
 
 from rustlearn import NamedArray
 from rustlearn.model_selection.types import TrainTestSplitReturn
@@ -13,7 +13,13 @@ class SimpleTrainTestSplit:
     _n: PySimpleTrainTestSplit = None
 
     def __init__(
-        self, x: List[NamedArray], y: NamedArray, train_proportion: float
+        self,
+        x: List[NamedArray],
+        y: NamedArray,
+        train_proportion: float,
+        shuffle: bool = False,
+        random_state: Optional[int] = None,
+        test_size: Optional[int] = None,
     ) -> None:
         """Initialize the class.
 
@@ -21,17 +27,31 @@ class SimpleTrainTestSplit:
             x: (List[NamedArray])
             y: (NamedArray)
             train_proportion: float
+            shuffle: (bool) shuffle the rows before splitting
+            random_state: (Optional[int]) seed for a reproducible shuffle
+            test_size: (Optional[int]) number of test rows, overrides
+                train_proportion
         :return:
             None
         """
         self.x = [nm._n for nm in x]
         self.y = y._n
         self.train_proportion = train_proportion
+        self.shuffle = shuffle
+        self.random_state = random_state
+        self.test_size = test_size
         self._n = PySimpleTrainTestSplit(
-            x=self.x, y=self.y, train_proportion=self.train_proportion
+            x=self.x,
+            y=self.y,
+            train_proportion=self.train_proportion,
+            shuffle=self.shuffle,
+            random_state=self.random_state,
+            test_size=self.test_size,
         )
         self.assert_equal_length(x, y)
         self.assert_logical_train_proportion(train_proportion)
+        if test_size is not None:
+            self.assert_logical_test_size(test_size, y.len())
 
     @classmethod
     def _from_py_simple_train_test_split(
@@ -69,6 +89,17 @@ class SimpleTrainTestSplit:
         """
         self._n.assert_logical_train_proportion(train_proportion)
 
+    def assert_logical_test_size(self, test_size: int, n_rows: int) -> None:
+        """Assert the test size makes sense.
+
+        :params:
+            test_size: int
+            n_rows: int
+        :return:
+            None
+        """
+        self._n.assert_logical_test_size(test_size, n_rows)
+
     def split(self) -> TrainTestSplitReturn:
         """Split the SimpleTrainTestSplit.
 
@@ -1,9 +1,14 @@
 use pyo3::prelude::*;
 use rustlearn_python::array::PyNamedArray;
-use rustlearn_python::exceptions::{NotYetImplementedError, RustLearnError, ValidationError};
+use rustlearn_python::exceptions::{
+    IoError, NotYetImplementedError, RustLearnError, ValidationError,
+};
 use rustlearn_python::linear_model::linear_regression::PyLinearRegression;
 use rustlearn_python::linear_model::PyLinearRegressionReturn;
+use rustlearn_python::metrics::classification_report::PyClassificationReport;
+use rustlearn_python::metrics::confusion_matrix::PyConfusionMatrix;
 use rustlearn_python::metrics::r_2;
+use rustlearn_python::metrics::{classification_report, confusion_matrix};
 use rustlearn_python::metrics::{mean_absolute_error, mean_squared_error};
 use rustlearn_python::model_selection::simple::PySimpleTrainTestSplit;
 use rustlearn_python::model_selection::PyTrainTestSplitReturn;
@@ -16,6 +21,8 @@ fn rustylearn(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<PyLinearRegression>().unwrap();
     m.add_class::<PySimpleTrainTestSplit>().unwrap();
     m.add_class::<PyTrainTestSplitReturn>().unwrap();
+    m.add_class::<PyConfusionMatrix>().unwrap();
+    m.add_class::<PyClassificationReport>().unwrap();
 
     // Metrics
     m.add_wrapped(wrap_pyfunction!(
@@ -29,6 +36,12 @@ fn rustylearn(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     ))
     .unwrap();
     m.add_wrapped(wrap_pyfunction!(r_2::py_r_2)).unwrap();
+    m.add_wrapped(wrap_pyfunction!(confusion_matrix::py_confusion_matrix))
+        .unwrap();
+    m.add_wrapped(wrap_pyfunction!(
+        classification_report::py_classification_report
+    ))
+    .unwrap();
 
     // Exceptions
     m.add("RustLearnError", py.get_type::<RustLearnError>())
@@ -40,5 +53,6 @@ fn rustylearn(py: Python, m: &Bound<PyModule>) -> PyResult<()> {
         py.get_type::<NotYetImplementedError>(),
     )
     .unwrap();
+    m.add("IoError", py.get_type::<IoError>()).unwrap();
     Ok(())
 }
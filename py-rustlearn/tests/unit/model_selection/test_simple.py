@@ -78,3 +78,30 @@ def test_split(x_2d, y_full) -> None:
         assert isclose(t, expected.y_train.data[j], rel_tol=0.001)
     for j, t in enumerate(res.y_test.data):
         assert isclose(t, expected.y_test.data[j], rel_tol=0.001)
+
+
+def test_split_shuffled_reproducible(x_2d, y_full) -> None:
+    first = SimpleTrainTestSplit(
+        x=x_2d, y=y_full, train_proportion=0.6, shuffle=True, random_state=42
+    ).split()
+    second = SimpleTrainTestSplit(
+        x=x_2d, y=y_full, train_proportion=0.6, shuffle=True, random_state=42
+    ).split()
+
+    assert first.y_train.data == second.y_train.data
+    assert first.y_test.data == second.y_test.data
+    assert sorted(first.y_train.data + first.y_test.data) == sorted(y_full.data)
+
+
+def test_split_test_size(x_2d, y_full) -> None:
+    res = SimpleTrainTestSplit(
+        x=x_2d, y=y_full, train_proportion=0.5, test_size=1
+    ).split()
+
+    assert res.y_train.data == [151.0, 75.0, 141.0, 206.0]
+    assert res.y_test.data == [135.0]
+
+
+def test_test_size_too_high(x_2d, y_full) -> None:
+    with pytest.raises(ValidationError):
+        SimpleTrainTestSplit(x=x_2d, y=y_full, train_proportion=0.5, test_size=5)
@@ -6,6 +6,8 @@ import pytest
 
 from rustlearn import NamedArray
 from rustlearn.metrics import (
+    classification_report,
+    confusion_matrix,
     mean_absolute_error,
     mean_squared_error,
     root_mean_squared_error,
@@ -93,3 +95,76 @@ def test_r_2() -> None:
     pred = NamedArray("y_pred", [140.0, 86.0, 120.0, 240.0, 140.0])
     true = NamedArray("y_true", [151.0, 75.0, 141.0, 206.0, 135.0])
     assert isclose(r_2(true, pred), 0.7861208004406095, rel_tol=0.001)
+
+
+def test_sample_weight() -> None:
+    """Integer weights match repeating the rows."""
+    weights = NamedArray("weights", [1, 1, 2])
+    true = NamedArray("y_true", [1, 2, 3])
+    pred = NamedArray("y_pred", [1, 2, 5])
+    assert isclose(mean_squared_error(true, pred, sample_weight=weights), 2)
+    assert isclose(mean_absolute_error(true, pred, sample_weight=weights), 1)
+    repeated_true = NamedArray("y_true", [1, 2, 3, 3])
+    repeated_pred = NamedArray("y_pred", [1, 2, 5, 5])
+    assert isclose(
+        r_2(true, pred, sample_weight=weights), r_2(repeated_true, repeated_pred)
+    )
+
+
+def test_sample_weight_raises() -> None:
+    """Simple test for raising the ValidationError on negative weights."""
+    true = NamedArray("y_true", [1, 2, 3])
+    with pytest.raises(ValidationError):
+        mean_squared_error(true, true, sample_weight=NamedArray("w", [1, -1, 1]))
+
+
+@pytest.fixture
+def labels_true() -> NamedArray:
+    """Fixture for the true class labels."""
+    return NamedArray("y_true", [0, 1, 2, 0, 1, 2])
+
+
+@pytest.fixture
+def labels_pred() -> NamedArray:
+    """Fixture for the predicted class labels."""
+    return NamedArray("y_pred", [0, 2, 1, 0, 0, 1])
+
+
+def test_confusion_matrix(labels_true, labels_pred) -> None:
+    """Simple test of confusion_matrix."""
+    cm = confusion_matrix(labels_true, labels_pred)
+    assert cm.labels == [0, 1, 2]
+    assert cm.matrix == [[2, 0, 0], [1, 0, 1], [0, 2, 0]]
+    named = cm.with_names(["cat", "dog", "rabbit"])
+    assert named.names == ["cat", "dog", "rabbit"]
+    assert str(named).splitlines()[0] == "true\\pred  cat  dog  rabbit"
+
+
+def test_confusion_matrix_normalize(labels_true, labels_pred) -> None:
+    """Simple test of a row-normalized confusion_matrix."""
+    cm = confusion_matrix(labels_true, labels_pred, normalize="true")
+    assert cm.matrix[1] == [0.5, 0, 0.5]
+
+
+def test_confusion_matrix_raises(labels_true, labels_pred) -> None:
+    """Simple test for raising the ValidationError."""
+    with pytest.raises(ValidationError):
+        confusion_matrix(labels_true, labels_pred, normalize="rows")
+    with pytest.raises(ValidationError):
+        confusion_matrix(labels_true, NamedArray("y_pred", [1, 2, 3]))
+
+
+def test_classification_report(labels_true, labels_pred) -> None:
+    """Simple test of classification_report."""
+    report = classification_report(labels_true, labels_pred)
+    assert isclose(report.classes["0"]["f1_score"], 0.8)
+    assert report.classes["0"]["support"] == 2
+    assert isclose(report.accuracy, 1 / 3)
+    assert isclose(report.macro_avg["precision"], 2 / 9)
+    assert "weighted avg" in str(report)
+
+
+def test_classification_report_raises(labels_true) -> None:
+    """Simple test for raising the ValidationError."""
+    with pytest.raises(ValidationError):
+        classification_report(labels_true, NamedArray("y_pred", [1, 2, 3]))